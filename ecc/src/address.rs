@@ -0,0 +1,233 @@
+/* Legacy Bitcoin addresses: Base58Check of a version byte and a 20-byte hash (chapters 4 and 8 of the book).
+*
+*  P2PKH pays to hash160 of a SEC public key, P2SH to hash160 of a redeem script. The version byte says which kind of
+*  hash it is and which network the address belongs to, which is why mainnet addresses start with 1 or 3 and testnet ones
+*  with m, n or 2. Regtest uses the testnet version bytes, so an Address for regtest is stored and compared as Testnet.
+*/
+
+use crate::base58::{decode_base58_checksum, encode_base58_checksum, Base58Error};
+use crate::hash::hash160;
+use crate::s256::S256Point;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Network {
+    Mainnet,
+    Testnet,
+    Regtest,
+}
+
+impl Network {
+    fn p2pkh_version(self) -> u8 {
+        match self {
+            Network::Mainnet => 0x00,
+            Network::Testnet | Network::Regtest => 0x6f,
+        }
+    }
+
+    fn p2sh_version(self) -> u8 {
+        match self {
+            Network::Mainnet => 0x05,
+            Network::Testnet | Network::Regtest => 0xc4,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Payload {
+    // hash160 of a SEC public key
+    PubkeyHash([u8; 20]),
+    // hash160 of a redeem script
+    ScriptHash([u8; 20]),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddressError {
+    Base58(Base58Error),
+    // The decoded data is not a version byte and a 20-byte hash
+    InvalidLength(usize),
+    UnknownVersion(u8),
+}
+
+impl fmt::Display for AddressError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AddressError::Base58(error) => write!(f, "{}", error),
+            AddressError::InvalidLength(len) => write!(f, "Address data is {} bytes instead of 21", len),
+            AddressError::UnknownVersion(version) => write!(f, "Unknown address version byte {:#04x}", version),
+        }
+    }
+}
+
+impl std::error::Error for AddressError {}
+
+impl From<Base58Error> for AddressError {
+    fn from(error: Base58Error) -> Self {
+        AddressError::Base58(error)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Address {
+    network: Network,
+    payload: Payload,
+}
+
+impl Address {
+    // Regtest is stored as Testnet: the two encode to the same string, so they must also be the same Address
+    pub fn new(network: Network, payload: Payload) -> Address {
+        let network = match network {
+            Network::Regtest => Network::Testnet,
+            network => network,
+        };
+        Address { network, payload }
+    }
+
+    /* Pays to the public key in its compressed or uncompressed SEC form; the two give different addresses. The point at
+    *  infinity has no public key encoding, and an address made from its 0x00 placeholder could never be spent.
+    */
+    pub fn p2pkh(point: &S256Point, compressed: bool, network: Network) -> Result<Address, &'static str> {
+        if point.is_infinity() {
+            return Err("Point at infinity has no address");
+        }
+        Ok(Address::new(network, Payload::PubkeyHash(hash160(&point.sec(compressed)))))
+    }
+
+    pub fn p2sh(redeem_script: &[u8], network: Network) -> Address {
+        Address::new(network, Payload::ScriptHash(hash160(redeem_script)))
+    }
+
+    // Mainnet or Testnet, never Regtest: regtest addresses use the testnet version bytes and cannot be told apart
+    pub fn network(&self) -> Network {
+        self.network
+    }
+
+    pub fn payload(&self) -> &Payload {
+        &self.payload
+    }
+
+    pub fn parse(text: &str) -> Result<Address, AddressError> {
+        let data = decode_base58_checksum(text)?;
+        if data.len() != 21 {
+            return Err(AddressError::InvalidLength(data.len()));
+        }
+        let hash: [u8; 20] = data[1..].try_into().unwrap();
+        let (network, payload) = match data[0] {
+            0x00 => (Network::Mainnet, Payload::PubkeyHash(hash)),
+            0x05 => (Network::Mainnet, Payload::ScriptHash(hash)),
+            0x6f => (Network::Testnet, Payload::PubkeyHash(hash)),
+            0xc4 => (Network::Testnet, Payload::ScriptHash(hash)),
+            version => return Err(AddressError::UnknownVersion(version)),
+        };
+        Ok(Address::new(network, payload))
+    }
+}
+
+impl fmt::Display for Address {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (version, hash) = match &self.payload {
+            Payload::PubkeyHash(hash) => (self.network.p2pkh_version(), hash),
+            Payload::ScriptHash(hash) => (self.network.p2sh_version(), hash),
+        };
+        let mut data = vec![version];
+        data.extend_from_slice(hash);
+        write!(f, "{}", encode_base58_checksum(&data))
+    }
+}
+
+impl FromStr for Address {
+    type Err = AddressError;
+
+    fn from_str(text: &str) -> Result<Address, AddressError> {
+        Address::parse(text)
+    }
+}
+
+impl S256Point {
+    // The P2PKH address of this public key, as the book's S256Point.address
+    pub fn address(&self, compressed: bool, network: Network) -> Result<String, &'static str> {
+        Address::p2pkh(self, compressed, network).map(|address| address.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecdsa::PrivateKey;
+    use crate::u256::U256;
+
+    fn bytes(hex: &str) -> Vec<u8> {
+        (0..hex.len()).step_by(2).map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap()).collect()
+    }
+
+    #[test]
+    fn test_p2pkh() {
+        // Chapter 4 of the book
+        let cases = [
+            (U256::from_u64(5002), false, Network::Testnet, "mmTPbXQFxboEtNRkwfh6K51jvdtHLxGeMA"),
+            (U256::from_u64(2020u64.pow(5)), true, Network::Testnet, "mopVkxp8UhXqRYbCYJsbeE1h1fiF64jcoH"),
+            (U256::from_u64(0x12345deadbeef), true, Network::Mainnet, "1F1Pn2y6pDb68E5nYJJeba4TLg2U7B6KF1"),
+        ];
+        for (secret, compressed, network, expected) in cases {
+            let key = PrivateKey::new(secret).unwrap();
+            assert_eq!(key.public_key().address(compressed, network).unwrap(), expected);
+            assert_eq!(key.address(compressed, network), expected);
+            let address = Address::parse(expected).unwrap();
+            assert_eq!(address, Address::p2pkh(key.public_key(), compressed, network).unwrap());
+            assert_eq!(address.to_string(), expected);
+        }
+
+        let g = S256Point::generator();
+        assert_eq!(g.address(true, Network::Mainnet).unwrap(), "1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMH");
+        assert_eq!(S256Point::infinity().address(true, Network::Mainnet), Err("Point at infinity has no address"));
+        assert_eq!(Address::p2pkh(&S256Point::infinity(), false, Network::Testnet), Err("Point at infinity has no address"));
+    }
+
+    #[test]
+    fn test_regtest() {
+        // Regtest addresses are the testnet ones, and an Address built for regtest round-trips through its string
+        let g = S256Point::generator();
+        let regtest = Address::p2pkh(&g, true, Network::Regtest).unwrap();
+        assert_eq!(regtest, Address::p2pkh(&g, true, Network::Testnet).unwrap());
+        assert_eq!(regtest.network(), Network::Testnet);
+        assert_eq!(Address::parse(&regtest.to_string()), Ok(regtest));
+        let script = Address::p2sh(b"redeem script", Network::Regtest);
+        assert_eq!(Address::parse(&script.to_string()), Ok(script));
+    }
+
+    #[test]
+    fn test_p2sh() {
+        // The 2-of-2 multisig redeem script of chapter 8
+        let redeem_script = bytes(
+            "5221022626e955ea6ea6d98850c994f9107b036b1334f18ca8830bfff1295d21cfdb702103b287eaf122eea69030a0e9feed096bed\
+             8045c8b98bec453e1ffac7fbdbd4bb7152ae",
+        );
+        let address = Address::p2sh(&redeem_script, Network::Mainnet);
+        assert_eq!(address.payload(), &Payload::ScriptHash(bytes("74d691da1574e6b3c192ecfb52cc8984ee7b6c56").try_into().unwrap()));
+        assert_eq!(address.to_string(), "3CLoMMyuoDQTPRD3XYZtCvgvkadrAdvdXh");
+        assert_eq!("3CLoMMyuoDQTPRD3XYZtCvgvkadrAdvdXh".parse(), Ok(address));
+
+        let testnet = Address::p2sh(&redeem_script, Network::Testnet);
+        assert_eq!(testnet.to_string(), "2N3u1R6uwQfuobCqbCgBkpsgBxvr1tZpe7B");
+        assert_eq!(Address::parse("2N3u1R6uwQfuobCqbCgBkpsgBxvr1tZpe7B"), Ok(testnet));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(matches!(
+            Address::parse("1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMj"),
+            Err(AddressError::Base58(Base58Error::ChecksumMismatch { .. }))
+        ));
+        assert_eq!(
+            Address::parse("1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAM0"),
+            Err(AddressError::Base58(Base58Error::InvalidCharacter { character: '0', position: 33 }))
+        );
+        assert_eq!(Address::parse(&encode_base58_checksum(&[0x00; 20])), Err(AddressError::InvalidLength(20)));
+        // A mainnet WIF private key is valid Base58Check but not an address
+        let mut data = vec![0x80];
+        data.extend([0x11; 20]);
+        assert_eq!(Address::parse(&encode_base58_checksum(&data)), Err(AddressError::UnknownVersion(0x80)));
+        assert_eq!(AddressError::UnknownVersion(0x80).to_string(), "Unknown address version byte 0x80");
+    }
+}
@@ -0,0 +1,195 @@
+/* Base58 and Base58Check, the text encodings of legacy addresses and WIF keys (chapter 4 of the book).
+*
+*  Base58 reads the bytes as one big-endian number and writes it in the alphabet below, which leaves out 0, O, I and l so
+*  that no two characters look alike. Every leading zero byte becomes a leading '1', since the number alone would lose
+*  them. Base58Check appends the first 4 bytes of hash256(payload) before encoding, which catches typos when decoding.
+*
+*  Unlike the rest of the crate, decoding reports a typed error: callers want to point at the offending character or tell
+*  a mistyped address (bad checksum) from text that is not base58 at all.
+*/
+
+use crate::hash::hash256;
+use std::fmt;
+
+const ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Base58Error {
+    // A character outside the alphabet, with its character index in the input
+    InvalidCharacter { character: char, position: usize },
+    // The last 4 bytes are not the checksum of the rest
+    ChecksumMismatch { expected: [u8; 4], found: [u8; 4] },
+    // Fewer than the 4 bytes of a checksum
+    TooShort,
+}
+
+impl fmt::Display for Base58Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Base58Error::InvalidCharacter { character, position } => {
+                write!(f, "Invalid base58 character {:?} at position {}", character, position)
+            }
+            Base58Error::ChecksumMismatch { expected, found } => write!(
+                f,
+                "Base58Check checksum mismatch: expected {:02x?}, found {:02x?}",
+                expected, found
+            ),
+            Base58Error::TooShort => write!(f, "Base58Check data is shorter than its checksum"),
+        }
+    }
+}
+
+impl std::error::Error for Base58Error {}
+
+pub fn encode_base58(bytes: &[u8]) -> String {
+    let zeros = bytes.iter().take_while(|&&byte| byte == 0).count();
+    // Base-58 digits of the number, least significant first; each input byte is digits = digits * 256 + byte
+    let mut digits: Vec<u8> = Vec::with_capacity(bytes.len() * 138 / 100 + 1);
+    for &byte in &bytes[zeros..] {
+        let mut carry = byte as u32;
+        for digit in digits.iter_mut() {
+            carry += (*digit as u32) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            digits.push((carry % 58) as u8);
+            carry /= 58;
+        }
+    }
+    let mut result = "1".repeat(zeros);
+    result.extend(digits.iter().rev().map(|&digit| ALPHABET[digit as usize] as char));
+    result
+}
+
+pub fn decode_base58(text: &str) -> Result<Vec<u8>, Base58Error> {
+    let zeros = text.chars().take_while(|&c| c == '1').count();
+    // Bytes of the number, least significant first; each character is bytes = bytes * 58 + digit
+    let mut bytes: Vec<u8> = Vec::with_capacity(text.len() * 733 / 1000 + 1);
+    for (position, character) in text.chars().enumerate().skip(zeros) {
+        let digit = ALPHABET
+            .iter()
+            .position(|&c| c as char == character)
+            .ok_or(Base58Error::InvalidCharacter { character, position })?;
+        let mut carry = digit as u32;
+        for byte in bytes.iter_mut() {
+            carry += *byte as u32 * 58;
+            *byte = carry as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.push(carry as u8);
+            carry >>= 8;
+        }
+    }
+    let mut result = vec![0u8; zeros];
+    result.extend(bytes.iter().rev());
+    Ok(result)
+}
+
+fn checksum(payload: &[u8]) -> [u8; 4] {
+    hash256(payload)[..4].try_into().unwrap()
+}
+
+pub fn encode_base58_checksum(payload: &[u8]) -> String {
+    let mut bytes = payload.to_vec();
+    bytes.extend(checksum(payload));
+    encode_base58(&bytes)
+}
+
+// Decodes and strips the checksum, returning only the payload
+pub fn decode_base58_checksum(text: &str) -> Result<Vec<u8>, Base58Error> {
+    let mut bytes = decode_base58(text)?;
+    if bytes.len() < 4 {
+        return Err(Base58Error::TooShort);
+    }
+    let found: [u8; 4] = bytes.split_off(bytes.len() - 4).try_into().unwrap();
+    let expected = checksum(&bytes);
+    if found != expected {
+        return Err(Base58Error::ChecksumMismatch { expected, found });
+    }
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bytes(hex: &str) -> Vec<u8> {
+        (0..hex.len()).step_by(2).map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap()).collect()
+    }
+
+    #[test]
+    fn test_encode_base58() {
+        // Chapter 4 of the book
+        let cases = [
+            ("7c076ff316692a3d7eb3c3bb0f8b1488cf72e1afcd929e29307032997a838a3d", "9MA8fRQrT4u8Zj8ZRd6MAiiyaxb2Y1CMpvVkHQu5hVM6"),
+            ("eff69ef2b1bd93a66ed5219add4fb51e11a840f404876325a1e8ffe0529a2c", "4fE3H2E6XMp4SsxtwinF7w9a34ooUrwWe4WsW1458Pd"),
+            ("c7207fee197d27c618aea621406f6bf5ef6fca38681d82b2f06fddbdce6feab6", "EQJsjkd6JaGwxrjEhfeqPenqHwrBmPQZjJGNSCHBkcF7"),
+        ];
+        for (hex, expected) in cases {
+            assert_eq!(encode_base58(&bytes(hex)), expected);
+            assert_eq!(decode_base58(expected), Ok(bytes(hex)));
+        }
+    }
+
+    #[test]
+    fn test_leading_zeros() {
+        assert_eq!(encode_base58(&[]), "");
+        assert_eq!(encode_base58(&[0]), "1");
+        assert_eq!(encode_base58(&[0, 0, 1]), "112");
+        assert_eq!(encode_base58(&[0, 0, 58]), "1121");
+        assert_eq!(decode_base58(""), Ok(vec![]));
+        assert_eq!(decode_base58("111"), Ok(vec![0, 0, 0]));
+        assert_eq!(decode_base58("1121"), Ok(vec![0, 0, 58]));
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let mut state: u64 = 0x2545f4914f6cdd1d;
+        for len in 0..40 {
+            let data: Vec<u8> = (0..len)
+                .map(|i| {
+                    state ^= state << 13;
+                    state ^= state >> 7;
+                    state ^= state << 17;
+                    // Some leading zeros in every other input
+                    if i < 3 && len % 2 == 0 { 0 } else { state as u8 }
+                })
+                .collect();
+            assert_eq!(decode_base58(&encode_base58(&data)), Ok(data.clone()));
+            assert_eq!(decode_base58_checksum(&encode_base58_checksum(&data)), Ok(data));
+        }
+    }
+
+    #[test]
+    fn test_base58_checksum() {
+        // The P2PKH address of the secret 1 with a compressed key
+        let mut payload = vec![0x00];
+        payload.extend(bytes("751e76e8199196d454941c45d1b3a323f1433bd6"));
+        assert_eq!(encode_base58_checksum(&payload), "1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMH");
+        assert_eq!(decode_base58_checksum("1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMH"), Ok(payload));
+
+        // One changed character is caught by the checksum
+        assert!(matches!(
+            decode_base58_checksum("1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMj"),
+            Err(Base58Error::ChecksumMismatch { .. })
+        ));
+        assert_eq!(decode_base58_checksum("111"), Err(Base58Error::TooShort));
+    }
+
+    #[test]
+    fn test_invalid_character() {
+        assert_eq!(decode_base58("12l4"), Err(Base58Error::InvalidCharacter { character: 'l', position: 2 }));
+        assert_eq!(decode_base58("0"), Err(Base58Error::InvalidCharacter { character: '0', position: 0 }));
+        assert_eq!(decode_base58("1é"), Err(Base58Error::InvalidCharacter { character: 'é', position: 1 }));
+        assert_eq!(
+            decode_base58_checksum("1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMI"),
+            Err(Base58Error::InvalidCharacter { character: 'I', position: 33 })
+        );
+        assert_eq!(
+            Base58Error::InvalidCharacter { character: 'O', position: 5 }.to_string(),
+            "Invalid base58 character 'O' at position 5"
+        );
+    }
+}
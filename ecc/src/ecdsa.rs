@@ -0,0 +1,702 @@
+/* ECDSA over secp256k1, following chapter 3 of the book.
+*
+*  Signing with secret e and nonce k:  R = k*G,  r = R.x mod N,  s = (z + r*e) / k mod N
+*  Verifying against P = e*G:          u = z/s,  v = r/s,  and u*G + v*P must have x coordinate r (mod N)
+*
+*  All of the arithmetic mod N goes through Scalar, which is constant-time, so r*e and the division by k in signing do not
+*  leak the key or the nonce any more than the secret multiplications (e*G, k*G) on the constant-time path do. The public
+*  multiplication in verification goes through multi_mul, which shares the doublings of u*G and v*P.
+*
+*  Nonces are derived from the key and the message with RFC 6979, like bitcoin-core/secp256k1's nonce_function_rfc6979:
+*  the same key and message always give the same signature, and no random number generator is needed to keep k secret.
+*/
+
+use crate::address::Network;
+use crate::hash::{hash256, hmac_sha256};
+use crate::s256::{S256Point, N};
+use crate::scalar::Scalar;
+use crate::u256::U256;
+use std::fmt;
+
+// r and s are both in [1, N - 1]; the constructor is the only way to build one
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Signature {
+    r: Scalar,
+    s: Scalar,
+}
+
+impl Signature {
+    pub fn new(r: U256, s: U256) -> Result<Signature, &'static str> {
+        let r = Scalar::from_u256(r).map_err(|_| "r is not below the group order")?;
+        let s = Scalar::from_u256(s).map_err(|_| "s is not below the group order")?;
+        if r.is_zero() || s.is_zero() {
+            return Err("r and s must be non-zero");
+        }
+        Ok(Signature { r, s })
+    }
+
+    pub fn r(&self) -> &Scalar {
+        &self.r
+    }
+
+    pub fn s(&self) -> &Scalar {
+        &self.s
+    }
+
+    /* (r, s) and (r, N - s) are both valid for the same message and key. BIP62 and BIP146 make the one with s <= N/2
+    *  the only standard form, so that a third party cannot change a transaction id by flipping s.
+    */
+    pub fn is_low_s(&self) -> bool {
+        !self.s.is_high()
+    }
+
+    // Replaces a high s by N - s; returns whether it was high, like secp256k1_ecdsa_signature_normalize
+    pub fn normalize_s(&mut self) -> bool {
+        let high = self.s.is_high();
+        if high {
+            self.s = -self.s;
+        }
+        high
+    }
+
+    // r below 2^255, so that DER does not need a padding byte for it (SigHasLowR in Bitcoin Core)
+    pub fn has_low_r(&self) -> bool {
+        self.r.to_be_bytes()[0] < 0x80
+    }
+
+    // SEQUENCE { INTEGER r, INTEGER s } with minimal integers: no leading zero byte unless the top bit would be set
+    pub fn der(&self) -> Vec<u8> {
+        let mut body = Vec::with_capacity(70);
+        for value in [&self.r, &self.s] {
+            let bytes = value.to_be_bytes();
+            let start = bytes.iter().position(|&byte| byte != 0).expect("r and s are non-zero");
+            let mut integer = bytes[start..].to_vec();
+            if integer[0] & 0x80 != 0 {
+                integer.insert(0, 0x00);
+            }
+            body.push(0x02);
+            body.push(integer.len() as u8);
+            body.extend(integer);
+        }
+        let mut der = vec![0x30, body.len() as u8];
+        der.extend(body);
+        der
+    }
+
+    /* Strict DER as required by BIP66 (IsValidSignatureEncoding in Bitcoin Core, without the trailing sighash byte):
+    *  short-form lengths that add up exactly, and integers that are neither empty, negative nor padded with a needless
+    *  zero byte. r and s must also be in [1, N - 1], which BIP66 leaves to the signature check.
+    */
+    pub fn parse(der: &[u8]) -> Result<Signature, &'static str> {
+        if der.len() < 8 || der.len() > 72 {
+            return Err("DER signature has an invalid length");
+        }
+        if der[0] != 0x30 {
+            return Err("DER signature is not a sequence");
+        }
+        if der[1] as usize != der.len() - 2 {
+            return Err("DER sequence length does not match the signature");
+        }
+        let r_len = der[3] as usize;
+        if 5 + r_len >= der.len() {
+            return Err("DER length of r runs past the signature");
+        }
+        let s_len = der[5 + r_len] as usize;
+        if r_len + s_len + 6 != der.len() {
+            return Err("DER lengths of r and s do not match the signature");
+        }
+        let r = strict_integer(&der[2..4 + r_len])?;
+        let s = strict_integer(&der[4 + r_len..])?;
+        Signature::new(r, s)
+    }
+
+    /* The lax parser Bitcoin Core uses for signatures from before BIP66 (contrib/lax_der_parsing.c in
+    *  bitcoin-core/secp256k1), which accepts what OpenSSL used to: any sequence length, long-form lengths, integers with
+    *  leading zeros or the top bit set (read as unsigned) and trailing bytes. Where that parser returns an all-zero
+    *  signature that can never verify, this returns an error.
+    */
+    pub fn parse_lax(input: &[u8]) -> Result<Signature, &'static str> {
+        if input.first() != Some(&0x30) {
+            return Err("DER signature is not a sequence");
+        }
+        let mut pos = 1;
+        let len = *input.get(pos).ok_or("DER signature is truncated")?;
+        pos += 1;
+        if len & 0x80 != 0 {
+            // The sequence length itself is ignored, only its bytes are skipped
+            let len_bytes = (len & 0x7f) as usize;
+            if len_bytes > input.len() - pos {
+                return Err("DER signature is truncated");
+            }
+            pos += len_bytes;
+        }
+        let r = lax_integer(input, &mut pos)?;
+        let s = lax_integer(input, &mut pos)?;
+        Signature::new(unsigned_integer(r)?, unsigned_integer(s)?)
+    }
+}
+
+// One INTEGER (tag, length and contents) under the BIP66 rules
+fn strict_integer(field: &[u8]) -> Result<U256, &'static str> {
+    if field[0] != 0x02 {
+        return Err("DER integer tag expected");
+    }
+    let contents = &field[2..];
+    if contents.is_empty() {
+        return Err("DER integer is empty");
+    }
+    if contents[0] & 0x80 != 0 {
+        return Err("DER integer is negative");
+    }
+    if contents.len() > 1 && contents[0] == 0x00 && contents[1] & 0x80 == 0 {
+        return Err("DER integer has excess padding");
+    }
+    unsigned_integer(contents)
+}
+
+// The contents of the INTEGER at input[*pos..], with a short or long-form length; moves pos past it
+fn lax_integer<'a>(input: &'a [u8], pos: &mut usize) -> Result<&'a [u8], &'static str> {
+    if input.get(*pos) != Some(&0x02) {
+        return Err("DER integer tag expected");
+    }
+    *pos += 1;
+    let mut len = *input.get(*pos).ok_or("DER signature is truncated")? as usize;
+    *pos += 1;
+    if len & 0x80 != 0 {
+        let mut len_bytes = len & 0x7f;
+        if len_bytes > input.len() - *pos {
+            return Err("DER signature is truncated");
+        }
+        while len_bytes > 0 && input[*pos] == 0x00 {
+            *pos += 1;
+            len_bytes -= 1;
+        }
+        if len_bytes >= std::mem::size_of::<usize>() {
+            return Err("DER integer length is too large");
+        }
+        len = 0;
+        for _ in 0..len_bytes {
+            len = (len << 8) | input[*pos] as usize;
+            *pos += 1;
+        }
+    }
+    if len > input.len() - *pos {
+        return Err("DER signature is truncated");
+    }
+    let contents = &input[*pos..*pos + len];
+    *pos += len;
+    Ok(contents)
+}
+
+// Big-endian bytes as an unsigned integer; leading zeros do not count towards the 32-byte limit
+fn unsigned_integer(bytes: &[u8]) -> Result<U256, &'static str> {
+    let start = bytes.iter().position(|&byte| byte != 0).unwrap_or(bytes.len());
+    let digits = &bytes[start..];
+    if digits.len() > 32 {
+        return Err("DER integer is too large");
+    }
+    let mut padded = [0u8; 32];
+    padded[32 - digits.len()..].copy_from_slice(digits);
+    Ok(U256::from_be_bytes(&padded))
+}
+
+// r and s are public, unlike most scalars, so Debug shows them instead of Scalar's redacted form
+impl fmt::Debug for Signature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Signature {{ r: {}, s: {} }}", self.r, self.s)
+    }
+}
+
+// Same text as the book's Signature.__repr__
+impl fmt::Display for Signature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Signature({:x},{:x})", self.r.to_u256(), self.s.to_u256())
+    }
+}
+
+// z for a message the way the book computes it: hash256 read as a big-endian integer
+pub fn hash_message(message: &[u8]) -> U256 {
+    U256::from_be_bytes(&hash256(message))
+}
+
+// The message hash as an integer mod N
+fn message_scalar(z: &U256) -> Scalar {
+    Scalar::from_u256(z.div_rem(&N).1).expect("reduced modulo N")
+}
+
+// x coordinate of a point as a scalar; p is only slightly larger than N, so this is at most one subtraction
+fn x_scalar(point: &S256Point) -> Option<Scalar> {
+    point.x().map(|x| Scalar::from_be_bytes_reduced(&x.to_be_bytes()))
+}
+
+impl S256Point {
+    /* Checks a signature on the message hash z. Besides the equation this rejects a u*G + v*P at infinity, which has no
+    *  x coordinate; r and s are in range by construction of Signature.
+    */
+    pub fn verify(&self, z: &U256, signature: &Signature) -> bool {
+        if self.is_infinity() {
+            return false;
+        }
+        let s_inv = signature.s.inverse().expect("s is non-zero");
+        let u = message_scalar(z) * s_inv;
+        let v = signature.r * s_inv;
+        let total = S256Point::multi_mul(&[(u, S256Point::generator()), (v, self.clone())]);
+        x_scalar(&total) == Some(signature.r)
+    }
+}
+
+pub struct PrivateKey {
+    secret: Scalar,
+    point: S256Point,
+}
+
+impl PrivateKey {
+    // The secret must be in [1, N - 1]
+    pub fn new(secret: U256) -> Result<PrivateKey, &'static str> {
+        let secret = Scalar::from_u256(secret)?;
+        if secret.is_zero() {
+            return Err("Secret must be non-zero");
+        }
+        Ok(PrivateKey { secret, point: S256Point::from_secret(&secret) })
+    }
+
+    pub fn public_key(&self) -> &S256Point {
+        &self.point
+    }
+
+    // The secret as 64 hex digits, like the book's PrivateKey.hex()
+    pub fn hex(&self) -> String {
+        self.secret.to_string()
+    }
+
+    // The P2PKH address that this key can spend from; the secret is non-zero, so the point is never at infinity
+    pub fn address(&self, compressed: bool, network: Network) -> String {
+        self.point.address(compressed, network).expect("the public key of a private key is not at infinity")
+    }
+
+    // Signs with the RFC 6979 nonce, so the same key and message always give the same signature
+    pub fn sign(&self, z: &U256) -> Signature {
+        self.sign_with_entropy(z, None)
+    }
+
+    /* Grinds for a signature with a low r the way Bitcoin Core's CKey::Sign does: the plain RFC 6979 signature first,
+    *  then the counter 1, 2, ... as 4 little-endian bytes of extra entropy until r < 2^255. With s always low, the DER
+    *  encoding is then at most 70 bytes (71 with the sighash byte). Each attempt succeeds with probability 1/2.
+    */
+    pub fn sign_low_r(&self, z: &U256) -> Signature {
+        let mut signature = self.sign(z);
+        let mut extra_entropy = [0u8; 32];
+        let mut counter: u32 = 0;
+        while !signature.has_low_r() {
+            counter += 1;
+            extra_entropy[..4].copy_from_slice(&counter.to_le_bytes());
+            signature = self.sign_with_entropy(z, Some(&extra_entropy));
+        }
+        signature
+    }
+
+    /* Signs with the RFC 6979 nonce seeded with 32 bytes of extra data as well, which gives a different but still
+    *  reproducible signature; Bitcoin Core passes a counter here to grind for a short r. Candidates that give a zero r or s
+    *  are skipped, as secp256k1_ecdsa_sign does by asking for the next nonce.
+    */
+    pub fn sign_with_entropy(&self, z: &U256, extra_entropy: Option<&[u8; 32]>) -> Signature {
+        self.nonces(z, extra_entropy)
+            .find_map(|k| self.sign_with_k(z, &k).ok())
+            .expect("the nonce generator does not run dry")
+    }
+
+    // The nonce `sign` uses for z
+    pub fn deterministic_k(&self, z: &U256) -> Scalar {
+        self.deterministic_k_with_entropy(z, None)
+    }
+
+    pub fn deterministic_k_with_entropy(&self, z: &U256, extra_entropy: Option<&[u8; 32]>) -> Scalar {
+        self.nonces(z, extra_entropy).next().expect("the nonce generator does not run dry")
+    }
+
+    /* RFC 6979 nonce candidates in [1, N - 1] for z. The seed is secret || (z mod N) || extra_entropy, each 32 big-endian
+    *  bytes, which is the key material of nonce_function_rfc6979 (that function's algo16 is only used by Schnorr signatures).
+    */
+    fn nonces<'a>(&self, z: &U256, extra_entropy: Option<&'a [u8; 32]>) -> impl Iterator<Item = Scalar> + 'a {
+        let secret = self.secret.to_be_bytes();
+        let message = message_scalar(z).to_be_bytes();
+        let mut seed: Vec<&[u8]> = vec![&secret, &message];
+        if let Some(extra) = extra_entropy {
+            seed.push(extra);
+        }
+        let mut generator = Rfc6979::new(&seed);
+        std::iter::from_fn(move || Some(generator.generate()))
+            .filter_map(|bytes| Scalar::from_be_bytes(&bytes).ok())
+            .filter(|k| !k.is_zero())
+    }
+
+    // The signing equation for a given nonce, for tests and for deterministic nonces. The result is always low-S.
+    pub fn sign_with_k(&self, z: &U256, k: &Scalar) -> Result<Signature, &'static str> {
+        let k_inv = k.inverse().map_err(|_| "Nonce must be non-zero")?;
+        let r = x_scalar(&S256Point::from_secret(k)).ok_or("Nonce must be non-zero")?;
+        let s = (message_scalar(z) + r * self.secret) * k_inv;
+        if r.is_zero() || s.is_zero() {
+            return Err("Nonce gives a zero r or s");
+        }
+        let mut signature = Signature { r, s };
+        signature.normalize_s();
+        Ok(signature)
+    }
+}
+
+/* The HMAC-SHA256 DRBG of RFC 6979 section 3.2, steps b to h, in the shape of secp256k1_rfc6979_hmac_sha256: every
+*  output after the first is preceded by K = HMAC_K(V || 0x00), V = HMAC_K(V), which is how step h.3 retries.
+*/
+struct Rfc6979 {
+    k: [u8; 32],
+    v: [u8; 32],
+    retry: bool,
+}
+
+impl Rfc6979 {
+    fn new(seed: &[&[u8]]) -> Rfc6979 {
+        let mut v = [0x01; 32];
+        let mut k = [0x00; 32];
+        for separator in [[0x00], [0x01]] {
+            let mut message: Vec<&[u8]> = vec![&v, &separator];
+            message.extend_from_slice(seed);
+            k = hmac_sha256(&k, &message);
+            v = hmac_sha256(&k, &[&v]);
+        }
+        Rfc6979 { k, v, retry: false }
+    }
+
+    fn generate(&mut self) -> [u8; 32] {
+        if self.retry {
+            self.k = hmac_sha256(&self.k, &[&self.v, &[0x00]]);
+            self.v = hmac_sha256(&self.k, &[&self.v]);
+        }
+        self.v = hmac_sha256(&self.k, &[&self.v]);
+        self.retry = true;
+        self.v
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::sha256;
+
+    fn book_point() -> S256Point {
+        S256Point::new(
+            U256::from_hex("887387e452b8eacc4acfde10d9aaf7f6d9a0f975aabb10d006e4da568744d06c"),
+            U256::from_hex("61de6d95231cd89026e286df3b6ae4a894a3378e393e93a0f45b666329a0ae34"),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_verify() {
+        // Chapter 3 of the book
+        let point = book_point();
+        let z = U256::from_hex("ec208baa0fc1c19f708a9ca96fdeff3ac3f230bb4a7ba4aede4942ad003c0f60");
+        let r = U256::from_hex("ac8d1c87e51d0d441be8b3dd5b05c8795b48875dffe00b7ffcfac23010d3a395");
+        let s = U256::from_hex("068342ceff8935ededd102dd876ffd6ba72d6a427a3edb13d26eb0781cb423c4");
+        assert!(point.verify(&z, &Signature::new(r, s).unwrap()));
+
+        let z = U256::from_hex("7c076ff316692a3d7eb3c3bb0f8b1488cf72e1afcd929e29307032997a838a3d");
+        let r = U256::from_hex("00eff69ef2b1bd93a66ed5219add4fb51e11a840f404876325a1e8ffe0529a2c");
+        let s = U256::from_hex("c7207fee197d27c618aea621406f6bf5ef6fca38681d82b2f06fddbdce6feab6");
+        let signature = Signature::new(r, s).unwrap();
+        assert!(point.verify(&z, &signature));
+
+        // Any change to the message, the signature or the key breaks it
+        assert!(!point.verify(&(z + U256::ONE), &signature));
+        assert!(!point.verify(&z, &Signature::new(r, s - U256::ONE).unwrap()));
+        assert!(!S256Point::generator().verify(&z, &signature));
+        assert!(!S256Point::infinity().verify(&z, &signature));
+    }
+
+    #[test]
+    fn test_signature_range() {
+        assert!(Signature::new(U256::ZERO, U256::ONE).is_err());
+        assert!(Signature::new(U256::ONE, U256::ZERO).is_err());
+        assert!(Signature::new(N, U256::ONE).is_err());
+        assert!(Signature::new(U256::ONE, N).is_err());
+        assert!(Signature::new(N - U256::ONE, N - U256::ONE).is_ok());
+    }
+
+    #[test]
+    fn test_sign() {
+        let key = PrivateKey::new(U256::from_u64(12345)).unwrap();
+        let z = U256::from_hex("969f6056aa26f7d2795fd013fe88868d09c9f6aed96965016e1936ae47060d48");
+        let signature = key.sign(&z);
+        assert!(key.public_key().verify(&z, &signature));
+        assert!(!signature.s().is_high());
+        assert_eq!(key.sign(&z), signature);
+        // A fixed nonce gives r = (k*G).x
+        let k = Scalar::from_u64(1234567890);
+        let signature = key.sign_with_k(&z, &k).unwrap();
+        assert_eq!(signature.r().to_u256(), S256Point::mul_generator(&k.to_u256()).x().unwrap().to_u256());
+        assert!(key.public_key().verify(&z, &signature));
+        assert!(key.sign_with_k(&z, &Scalar::ZERO).is_err());
+    }
+
+    #[test]
+    fn test_sign_matches_reference() {
+        // The constant-time scalar arithmetic of sign_with_k against the variable-time U256 formula, for random keys and nonces
+        let mut state: u64 = 0x1d8e4e27c47d124f;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+        let mut scalar = move || U256::from_limbs([next(), next(), next(), next()]) % N;
+        for _ in 0..10 {
+            let (secret, k, z) = (scalar(), scalar(), scalar());
+            let key = PrivateKey::new(secret).unwrap();
+            let signature = key.sign_with_k(&z, &Scalar::from_u256(k).unwrap()).unwrap();
+            let r = signature.r().to_u256();
+            let k_inv = crate::u256::inv_mod_divsteps(&k, &N).unwrap();
+            let s = z.add_mod(&r.mul_mod(&secret, &N), &N).mul_mod(&k_inv, &N);
+            let low_s = if s > (N - U256::ONE) >> 1 { N - s } else { s };
+            assert_eq!(signature.s().to_u256(), low_s);
+        }
+    }
+
+    fn message_hash(message: &[u8]) -> U256 {
+        U256::from_be_bytes(&sha256(message))
+    }
+
+    #[test]
+    fn test_deterministic_k() {
+        // The secp256k1 RFC 6979 vectors used by python-ecdsa and Trezor
+        let cases = [
+            ("01", "Satoshi Nakamoto", "8f8a276c19f4149656b280621e358cce24f5f52542772691ee69063b74f15d15"),
+            (
+                "01",
+                "All those moments will be lost in time, like tears in rain. Time to die...",
+                "38aa22d72376b4dbc472e06c3ba403ee0a394da63fc58d88686c611aba98d6b3",
+            ),
+            (
+                "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364140",
+                "Satoshi Nakamoto",
+                "33a19b60e25fb6f4435af53a3d42d493644827367e6453928554f43e49aa6f90",
+            ),
+            (
+                "f8b8af8ce3c7cca5e300d33939540c10d45ce001b8f252bfbc57ba0342904181",
+                "Alan Turing",
+                "525a82b70e67874398067543fd84c83d30c175fdc45fdeee082fe13b1d7cfdf1",
+            ),
+        ];
+        for (secret, message, k) in cases {
+            let key = PrivateKey::new(U256::from_hex(secret)).unwrap();
+            assert_eq!(key.deterministic_k(&message_hash(message.as_bytes())).to_u256(), U256::from_hex(k));
+        }
+
+        // The signature for the first vector, with s already low
+        let key = PrivateKey::new(U256::ONE).unwrap();
+        let z = message_hash(b"Satoshi Nakamoto");
+        let signature = key.sign(&z);
+        assert_eq!(signature.r().to_u256(), U256::from_hex("934b1ea10a4b3c1757e2b0c017d0b6143ce3c9a7e6a4a49860d7a6ab210ee3d8"));
+        assert_eq!(signature.s().to_u256(), U256::from_hex("2442ce9d2b916064108014783e923ec36b49743e2ffa1c4496f01a512aafd9e5"));
+    }
+
+    #[test]
+    fn test_deterministic_k_with_entropy() {
+        // Bitcoin Core's first grinding attempt: the counter 1 as 4 little-endian bytes, zero-padded to 32
+        let key = PrivateKey::new(U256::ONE).unwrap();
+        let z = message_hash(b"Satoshi Nakamoto");
+        let mut extra = [0u8; 32];
+        extra[..4].copy_from_slice(&1u32.to_le_bytes());
+        let k = key.deterministic_k_with_entropy(&z, Some(&extra));
+        assert_eq!(k.to_u256(), U256::from_hex("b8e91d19741f580eb14a4489493c085b7618caabcd0220cb0ac29161d9ce38a3"));
+        assert_eq!(key.deterministic_k_with_entropy(&z, None), key.deterministic_k(&z));
+
+        let signature = key.sign_with_entropy(&z, Some(&extra));
+        assert_eq!(signature, key.sign_with_k(&z, &k).unwrap());
+        assert_ne!(signature, key.sign(&z));
+        assert!(key.public_key().verify(&z, &signature));
+    }
+
+    #[test]
+    fn test_rfc6979_generator() {
+        // Only the concatenation of the seed parts matters, and every output moves the state on
+        let mut generator = Rfc6979::new(&[b"seed"]);
+        let first = generator.generate();
+        let second = generator.generate();
+        assert_ne!(first, second);
+        let mut again = Rfc6979::new(&[b"se", b"ed"]);
+        assert_eq!(again.generate(), first);
+        assert_eq!(again.generate(), second);
+    }
+
+    #[test]
+    fn test_sign_book() {
+        // Chapter 3 of the book: secret 12345, z = hash256("Programming Bitcoin!"), k = 1234567890
+        let key = PrivateKey::new(U256::from_u64(12345)).unwrap();
+        let z = hash_message(b"Programming Bitcoin!");
+        assert_eq!(z, U256::from_hex("969f6056aa26f7d2795fd013fe88868d09c9f6aed96965016e1936ae47060d48"));
+        let signature = key.sign_with_k(&z, &Scalar::from_u64(1234567890)).unwrap();
+        assert_eq!(signature.r().to_u256(), U256::from_hex("2b698a0f0a4041b77e63488ad48c23e8e8838dd1fb7520408b121697b782ef22"));
+        assert_eq!(signature.s().to_u256(), U256::from_hex("1dbc63bfef4416705e602a7b564161167076d8b20990a0f26f316cff2cb0bc1a"));
+    }
+
+    #[test]
+    fn test_private_key() {
+        assert!(PrivateKey::new(U256::ZERO).is_err());
+        assert!(PrivateKey::new(N).is_err());
+        let key = PrivateKey::new(U256::from_u64(7)).unwrap();
+        assert_eq!(key.public_key(), &S256Point::generator().rmul(&U256::from_u64(7)));
+        assert_eq!(key.hex(), format!("{:064x}", 7));
+    }
+
+    #[test]
+    fn test_low_s() {
+        let key = PrivateKey::new(U256::from_u64(12345)).unwrap();
+        let z = U256::from_hex("969f6056aa26f7d2795fd013fe88868d09c9f6aed96965016e1936ae47060d48");
+        let signature = key.sign(&z);
+        assert!(signature.is_low_s());
+
+        // The high-S twin verifies just as well, normalizing turns it back
+        let mut high = Signature::new(signature.r().to_u256(), (-*signature.s()).to_u256()).unwrap();
+        assert!(!high.is_low_s());
+        assert!(key.public_key().verify(&z, &high));
+        assert!(high.normalize_s());
+        assert_eq!(high, signature);
+        assert!(!high.normalize_s());
+        assert_eq!(high, signature);
+
+        // s = N/2 is the largest low value
+        let half = (N - U256::ONE) >> 1;
+        assert!(Signature::new(U256::ONE, half).unwrap().is_low_s());
+        assert!(!Signature::new(U256::ONE, half + U256::ONE).unwrap().is_low_s());
+    }
+
+    #[test]
+    fn test_low_r() {
+        let key = PrivateKey::new(U256::from_u64(12345)).unwrap();
+        let mut ground = 0;
+        for i in 0..16u64 {
+            let z = U256::from_u64(i);
+            let signature = key.sign_low_r(&z);
+            assert!(signature.has_low_r() && signature.is_low_s());
+            assert!(key.public_key().verify(&z, &signature));
+            assert!(signature.der().len() <= 70);
+            if key.sign(&z).has_low_r() {
+                assert_eq!(signature, key.sign(&z));
+            } else {
+                ground += 1;
+            }
+        }
+        assert!(ground > 0, "some of the plain signatures should have had a high r");
+
+        // The attempt after the plain signature uses the counter 1
+        let mut one = [0u8; 32];
+        one[0] = 1;
+        let z = (0..)
+            .map(U256::from_u64)
+            .find(|z| !key.sign(z).has_low_r() && key.sign_with_entropy(z, Some(&one)).has_low_r())
+            .unwrap();
+        assert_eq!(key.sign_low_r(&z), key.sign_with_entropy(&z, Some(&one)));
+    }
+
+    fn bytes(hex: &str) -> Vec<u8> {
+        (0..hex.len()).step_by(2).map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap()).collect()
+    }
+
+    #[test]
+    fn test_der() {
+        // Chapter 4 of the book
+        let r = U256::from_hex("37206a0610995c58074999cb9767b87af4c4978db68c06e8e6e81d282047a7c6");
+        let s = U256::from_hex("8ca63759c1157ebeaec0d03cecca119fc9a75bf8e6d0fa65c841c8e2738cdaec");
+        let signature = Signature::new(r, s).unwrap();
+        let der = bytes(
+            "3045022037206a0610995c58074999cb9767b87af4c4978db68c06e8e6e81d282047a7c6022100\
+             8ca63759c1157ebeaec0d03cecca119fc9a75bf8e6d0fa65c841c8e2738cdaec",
+        );
+        assert_eq!(signature.der(), der);
+        assert_eq!(Signature::parse(&der), Ok(signature));
+        assert_eq!(Signature::parse_lax(&der), Ok(signature));
+
+        // Small values shrink to one byte each, a top bit gets a zero in front
+        let signature = Signature::new(U256::ONE, U256::from_u64(0x80)).unwrap();
+        assert_eq!(signature.der(), bytes("300702010102020080"));
+        assert_eq!(Signature::parse(&signature.der()), Ok(signature));
+
+        let key = PrivateKey::new(U256::from_u64(12345)).unwrap();
+        for i in 0..16u64 {
+            let signature = key.sign(&U256::from_u64(i));
+            assert_eq!(Signature::parse(&signature.der()), Ok(signature));
+        }
+    }
+
+    #[test]
+    fn test_parse_strict() {
+        assert!(Signature::parse(&bytes("3006020101020102")).is_ok());
+        assert_eq!(Signature::parse(&bytes("30050201010200")), Err("DER signature has an invalid length"));
+        assert_eq!(Signature::parse(&[0x30; 73]), Err("DER signature has an invalid length"));
+        let cases = [
+            ("310702010102020080", "DER signature is not a sequence"),
+            ("300802010102020080", "DER sequence length does not match the signature"),
+            ("300702050102020080", "DER length of r runs past the signature"),
+            ("300702010102030080", "DER lengths of r and s do not match the signature"),
+            ("300703010102020080", "DER integer tag expected"),
+            ("3006020002020080", "DER integer is empty"),
+            ("300702018102020080", "DER integer is negative"),
+            ("30080202000102020080", "DER integer has excess padding"),
+            ("30080201010203000080", "DER integer has excess padding"),
+            ("300702010002020080", "r and s must be non-zero"),
+            ("30070201010201800000", "DER sequence length does not match the signature"),
+        ];
+        for (hex, error) in cases {
+            assert_eq!(Signature::parse(&bytes(hex)), Err(error), "{}", hex);
+        }
+        // Trailing bytes after a valid signature
+        assert!(Signature::parse(&bytes("30070201010202008000")).is_err());
+        // r = N is not a valid scalar even though the encoding is fine
+        let mut der = vec![0x30, 0x26, 0x02, 0x21, 0x00];
+        der.extend(N.to_be_bytes());
+        der.extend([0x02, 0x01, 0x01]);
+        assert_eq!(Signature::parse(&der), Err("r is not below the group order"));
+    }
+
+    #[test]
+    fn test_parse_lax() {
+        let expected = Ok(Signature::new(U256::ONE, U256::from_u64(0x80)).unwrap());
+        let cases = [
+            // Wrong or long-form sequence length, and trailing bytes
+            "302002010102020080",
+            "3082000002010102020080",
+            "300702010102020080ffff",
+            // Excess padding and a negative-looking s
+            "300b0203000001020400000080",
+            "3006020101020180",
+            // Long-form integer lengths, with leading zeros in the length itself
+            "30080281010102020080",
+            "300a028300000101020180",
+        ];
+        for hex in cases {
+            assert_eq!(Signature::parse_lax(&bytes(hex)), expected, "{}", hex);
+            assert!(Signature::parse(&bytes(hex)).is_err(), "{}", hex);
+        }
+        // Values of up to 32 bytes once the zeros are stripped
+        let mut der = vec![0x30, 0x00, 0x02, 0x28];
+        der.extend([0u8; 8]);
+        der.extend((N - U256::ONE).to_be_bytes());
+        der.extend([0x02, 0x01, 0x01]);
+        assert!(Signature::parse_lax(&der).is_ok());
+
+        assert_eq!(Signature::parse_lax(&bytes("3007020101020200")), Err("DER signature is truncated"));
+        assert_eq!(Signature::parse_lax(&bytes("30070201010302")), Err("DER integer tag expected"));
+        assert_eq!(Signature::parse_lax(&bytes("300002010102880101010101010101")), Err("DER integer length is too large"));
+        assert_eq!(Signature::parse_lax(&bytes("300002010102010002")), Err("r and s must be non-zero"));
+        let mut der = vec![0x30, 0x00, 0x02, 0x21, 0x01];
+        der.extend([0u8; 32]);
+        der.extend([0x02, 0x01, 0x01]);
+        assert_eq!(Signature::parse_lax(&der), Err("DER integer is too large"));
+    }
+
+    #[test]
+    fn test_display() {
+        let signature = Signature::new(U256::from_u64(0xab), U256::from_u64(0xcd)).unwrap();
+        assert_eq!(signature.to_string(), "Signature(ab,cd)");
+        assert_eq!(format!("{:?}", signature), format!("Signature {{ r: {:064x}, s: {:064x} }}", 0xab, 0xcd));
+    }
+}
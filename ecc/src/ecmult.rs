@@ -0,0 +1,342 @@
+/* Scalar multiplication strategies, after bitcoin-core/secp256k1's ecmult_impl.h and ecmult_gen_impl.h.
+*
+*  - mul_double_and_add: one doubling per bit and one addition per set bit. Kept as the reference the others are tested
+*    against.
+*  - mul_window: fixed windows of w bits over a table of 0..2^w multiples, so one addition per w bits.
+*  - mul_wnaf: width-w non-adjacent form. Digits are odd and in (-2^(w-1), 2^(w-1)), and any two non-zero digits are at
+*    least w positions apart, so there are about 256/(w+1) additions over a table of only 2^(w-2) odd multiples.
+*    Negative digits are free because negating a point only negates y.
+*  - mul_generator: k*G over a table of j*16^i*G for every 4-bit window i, built once on first use. The doublings are all
+*    in the table, so k*G is at most 64 mixed additions.
+*  - multi_mul: sum of k_i*P_i, as needed for u*G + v*P in verification and for batch verification. Small sums use
+*    Strauss' method (the wNAFs of all scalars are walked together, so the 256 doublings are shared); large ones use
+*    Pippenger's bucket method, whose cost per point falls as the number of points grows.
+*
+*  All of these branch on the digits of the scalar and are only meant for public scalars (verification, tests).
+*/
+
+use crate::field_5x52::S256Field;
+use crate::jacobian::JacobianPoint;
+use crate::s256;
+use crate::u256::U256;
+use crate::{Curve, Field, Point};
+use std::sync::OnceLock;
+
+// Window of the wNAF used by Point::rmul_u256; 8 precomputed points, as ECMULT_WINDOW_SIZE for a single point
+pub const WNAF_WINDOW: u32 = 5;
+// Window of the generator table: 64 rows of 15 points
+pub(crate) const G_WINDOW: u32 = 4;
+// From this many points on, multi_mul switches from Strauss to Pippenger (ECMULT_PIPPENGER_THRESHOLD)
+pub const PIPPENGER_THRESHOLD: usize = 88;
+
+// `count` (at most 64) bits of k starting at bit `position`
+pub(crate) fn bits_at(k: &U256, position: u32, count: u32) -> u64 {
+    let mask = if count == 64 { u64::MAX } else { (1 << count) - 1 };
+    (*k >> position).low_u64() & mask
+}
+
+pub fn mul_double_and_add<'c, F: Field>(point: &Point<'c, F>, k: &U256) -> Point<'c, F> {
+    let mut result = JacobianPoint::infinity(point.curve);
+    for i in (0..k.bits()).rev() {
+        result = result.double();
+        if k.bit(i) {
+            result = result.add_affine(point);
+        }
+    }
+    result.to_affine()
+}
+
+pub fn mul_window<'c, F: Field>(point: &Point<'c, F>, k: &U256, w: u32) -> Point<'c, F> {
+    assert!((1..=8).contains(&w), "window must be between 1 and 8 bits");
+    // table[j] = j*P
+    let mut table = vec![JacobianPoint::infinity(point.curve)];
+    for j in 1..(1usize << w) {
+        table.push(table[j - 1].add_affine(point));
+    }
+
+    let windows = k.bits().div_ceil(w);
+    let mut result = JacobianPoint::infinity(point.curve);
+    for i in (0..windows).rev() {
+        for _ in 0..w {
+            result = result.double();
+        }
+        let digit = bits_at(k, i * w, w) as usize;
+        if digit != 0 {
+            result = result.add(&table[digit]);
+        }
+    }
+    result.to_affine()
+}
+
+/* Width-w NAF of k, least significant digit first, as secp256k1_ecmult_wnaf. Instead of subtracting the digit from k
+*  it keeps a carry, so no intermediate value can overflow 256 bits; the result has one more digit than k has bits.
+*/
+pub fn wnaf(k: &U256, w: u32) -> Vec<i32> {
+    assert!((2..=31).contains(&w), "wNAF window must be between 2 and 31 bits");
+    let len = k.bits() + 1;
+    let mut digits = vec![0i32; len as usize];
+    let mut carry = 0u64;
+    let mut bit = 0;
+    while bit < len {
+        if k.bit(bit) as u64 == carry {
+            bit += 1;
+            continue;
+        }
+        let now = w.min(len - bit);
+        let mut word = bits_at(k, bit, now) as i64 + carry as i64;
+        carry = ((word >> (w - 1)) & 1) as u64;
+        word -= (carry as i64) << w;
+        digits[bit as usize] = word as i32;
+        bit += now;
+    }
+    debug_assert_eq!(carry, 0);
+    digits
+}
+
+// Odd multiples P, 3P, ..., (2^(w-1) - 1)P for the wNAF digits
+fn odd_multiples<'c, F: Field>(point: &Point<'c, F>, w: u32) -> Vec<JacobianPoint<'c, F>> {
+    let base = JacobianPoint::from_affine(point);
+    let double = base.double();
+    let mut table = vec![base];
+    for i in 1..(1usize << (w - 2)) {
+        table.push(table[i - 1].add(&double));
+    }
+    table
+}
+
+pub(crate) fn mul_wnaf<'c, F: Field>(point: &Point<'c, F>, k: &U256, w: u32) -> Point<'c, F> {
+    let table = odd_multiples(point, w);
+    let mut result = JacobianPoint::infinity(point.curve);
+    for &digit in wnaf(k, w).iter().rev() {
+        result = result.double();
+        result = add_digit(&result, &table, digit);
+    }
+    result.to_affine()
+}
+
+// result + digit*P for an odd wNAF digit, or result itself for 0
+fn add_digit<'c, F: Field>(result: &JacobianPoint<'c, F>, table: &[JacobianPoint<'_, F>], digit: i32) -> JacobianPoint<'c, F> {
+    if digit > 0 {
+        result.add(&table[(digit as usize - 1) / 2])
+    } else if digit < 0 {
+        result.add(&table[(-digit as usize - 1) / 2].neg())
+    } else {
+        result.clone()
+    }
+}
+
+// Strauss: one table and one wNAF per term, walked together under a single chain of doublings
+pub(crate) fn multi_mul_strauss<'c, F: Field>(curve: &'c Curve<F>, terms: &[(U256, &Point<'_, F>)]) -> Point<'c, F> {
+    let tables: Vec<_> = terms.iter().map(|(_, point)| odd_multiples(point, WNAF_WINDOW)).collect();
+    let wnafs: Vec<_> = terms.iter().map(|(k, _)| wnaf(k, WNAF_WINDOW)).collect();
+    let len = wnafs.iter().map(Vec::len).max().unwrap_or(0);
+
+    let mut result = JacobianPoint::infinity(curve);
+    for i in (0..len).rev() {
+        result = result.double();
+        for (digits, table) in wnafs.iter().zip(&tables) {
+            if let Some(&digit) = digits.get(i) {
+                result = add_digit(&result, table, digit);
+            }
+        }
+    }
+    result.to_affine()
+}
+
+// Bucket window for n points, after secp256k1_pippenger_bucket_window
+fn pippenger_window(n: usize) -> u32 {
+    match n {
+        0..=1 => 1,
+        2..=4 => 2,
+        5..=20 => 3,
+        21..=57 => 4,
+        58..=136 => 5,
+        137..=235 => 6,
+        236..=1260 => 7,
+        1261..=4420 => 9,
+        4421..=7880 => 10,
+        _ => 11,
+    }
+}
+
+/* Pippenger: for every c-bit window, drop each point into the bucket of its digit, then sum the buckets as
+*  1*B1 + 2*B2 + ... through running sums, which costs two additions per bucket instead of a multiplication.
+*/
+pub(crate) fn multi_mul_pippenger<'c, F: Field>(curve: &'c Curve<F>, terms: &[(U256, &Point<'_, F>)]) -> Point<'c, F> {
+    let c = pippenger_window(terms.len());
+    let bits = terms.iter().map(|(k, _)| k.bits()).max().unwrap_or(0);
+    let windows = bits.div_ceil(c);
+
+    let mut result = JacobianPoint::infinity(curve);
+    for i in (0..windows).rev() {
+        for _ in 0..c {
+            result = result.double();
+        }
+        let mut buckets = vec![JacobianPoint::infinity(curve); (1 << c) - 1];
+        for (k, point) in terms {
+            let digit = bits_at(k, i * c, c) as usize;
+            if digit != 0 {
+                buckets[digit - 1] = buckets[digit - 1].add_affine(point);
+            }
+        }
+        // running = B_j + ... + B_top, and window_sum adds running once per j, giving sum of j*B_j
+        let mut running = JacobianPoint::infinity(curve);
+        let mut window_sum = JacobianPoint::infinity(curve);
+        for bucket in buckets.iter().rev() {
+            running = running.add(bucket);
+            window_sum = window_sum.add(&running);
+        }
+        result = result.add(&window_sum);
+    }
+    result.to_affine()
+}
+
+pub(crate) fn multi_mul<'c, F: Field>(curve: &'c Curve<F>, terms: &[(U256, &Point<'_, F>)]) -> Point<'c, F> {
+    if terms.len() < PIPPENGER_THRESHOLD {
+        multi_mul_strauss(curve, terms)
+    } else {
+        multi_mul_pippenger(curve, terms)
+    }
+}
+
+// Row i holds j*16^i*G for j = 1..15 in affine coordinates, so the additions can use the mixed formula
+pub(crate) fn generator_table() -> &'static Vec<Vec<Point<'static, S256Field>>> {
+    static TABLE: OnceLock<Vec<Vec<Point<'static, S256Field>>>> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        let g = s256::curve().generator().expect("secp256k1 has a generator");
+        let mut base = JacobianPoint::from_affine(&g);
+        let row_len = (1usize << G_WINDOW) - 1;
+        let mut points = Vec::new();
+        for _ in 0..256 / G_WINDOW {
+            points.push(base.clone());
+            for _ in 1..row_len {
+                points.push(points[points.len() - 1].add(&base));
+            }
+            for _ in 0..G_WINDOW {
+                base = base.double();
+            }
+        }
+        // All 960 points are converted with one inversion
+        let affine = JacobianPoint::to_affine_batch(&points);
+        affine.chunks(row_len).map(<[_]>::to_vec).collect()
+    })
+}
+
+// k*G for k already reduced modulo N
+pub(crate) fn mul_generator(k: &U256) -> Point<'static, S256Field> {
+    let table = generator_table();
+    let mut result = JacobianPoint::infinity(s256::curve());
+    for (i, row) in table.iter().enumerate() {
+        let digit = bits_at(k, i as u32 * G_WINDOW, G_WINDOW) as usize;
+        if digit != 0 {
+            result = result.add_affine(&row[digit - 1]);
+        }
+    }
+    result.to_affine()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::s256::N;
+    use crate::{Curve, FieldElement, F223};
+
+    fn fe(value: u64) -> FieldElement<F223> {
+        FieldElement::new(U256::from_u64(value)).unwrap()
+    }
+
+    fn samples() -> Vec<U256> {
+        let mut state: u64 = 0x2545f4914f6cdd1d;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+        let mut values = vec![U256::ZERO, U256::ONE, U256::from_u64(15), U256::from_u64(16), N - U256::ONE, U256::MAX];
+        for _ in 0..6 {
+            values.push(U256::from_limbs([next(), next(), next(), next()]));
+        }
+        values
+    }
+
+    #[test]
+    fn test_wnaf_recodes() {
+        for k in samples() {
+            for w in [2, 4, 5, 8] {
+                let digits = wnaf(&k, w);
+                // Sum of d_i * 2^i gives k back, computed modulo 2^256 with wrapping arithmetic
+                let mut value = U256::ZERO;
+                for (i, &d) in digits.iter().enumerate() {
+                    let term = U256::from_u64(d.unsigned_abs() as u64) << i as u32;
+                    value = if d >= 0 { value.wrapping_add(&term) } else { value.wrapping_sub(&term) };
+                }
+                assert_eq!(value, k);
+
+                let non_zero: Vec<usize> = (0..digits.len()).filter(|&i| digits[i] != 0).collect();
+                for &i in &non_zero {
+                    assert!(digits[i] % 2 != 0 && digits[i].unsigned_abs() < 1 << (w - 1));
+                }
+                assert!(non_zero.windows(2).all(|pair| pair[1] - pair[0] >= w as usize));
+            }
+        }
+    }
+
+    #[test]
+    fn test_strategies_agree_small_curve() {
+        // (47, 71) has order 21 on the book's curve, so every multiple can be checked by repeated addition
+        let curve = Curve::new(fe(0), fe(7)).unwrap();
+        let g = curve.point(fe(47), fe(71)).unwrap();
+        let mut expected = curve.infinity();
+        for k in 0..50u64 {
+            let k256 = U256::from_u64(k);
+            assert_eq!(mul_double_and_add(&g, &k256), expected);
+            assert_eq!(mul_window(&g, &k256, 3), expected);
+            assert_eq!(mul_wnaf(&g, &k256, 4), expected);
+            expected = expected.add(&g).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_strategies_agree_secp256k1() {
+        let g = s256::curve().generator().unwrap();
+        for k in samples() {
+            let k = k.div_rem(&N).1;
+            let expected = mul_double_and_add(&g, &k);
+            assert_eq!(mul_window(&g, &k, 4), expected);
+            assert_eq!(mul_wnaf(&g, &k, WNAF_WINDOW), expected);
+            assert_eq!(mul_generator(&k), expected);
+        }
+        assert!(mul_wnaf(&g, &N, WNAF_WINDOW).is_infinity());
+    }
+
+    // Sum of separate multiplications, the reference for multi_mul
+    fn naive_sum<'c>(curve: &'c Curve<S256Field>, terms: &[(U256, &Point<'_, S256Field>)]) -> Point<'c, S256Field> {
+        let mut sum = curve.infinity();
+        for (k, point) in terms {
+            sum = sum.add(&mul_double_and_add(point, k)).unwrap();
+        }
+        sum
+    }
+
+    #[test]
+    fn test_multi_mul() {
+        let curve = s256::curve();
+        let mut scalars = samples().into_iter().map(|k| k.div_rem(&N).1).cycle();
+        let points: Vec<_> = (1..=100u64).map(|i| mul_generator(&U256::from_u64(i * 0x1234567))).collect();
+
+        for n in [0, 1, 2, 5, 25, 100] {
+            let terms: Vec<(U256, &Point<S256Field>)> = points.iter().take(n).map(|p| (scalars.next().unwrap(), p)).collect();
+            let expected = naive_sum(curve, &terms);
+            assert_eq!(multi_mul_strauss(curve, &terms), expected);
+            assert_eq!(multi_mul_pippenger(curve, &terms), expected);
+            assert_eq!(multi_mul(curve, &terms), expected);
+        }
+
+        // Terms that cancel: k*P + (N-k)*P
+        let k = U256::from_u64(987654321);
+        let p = &points[3];
+        assert!(multi_mul_strauss(curve, &[(k, p), (N - k, p)]).is_infinity());
+        assert!(multi_mul_pippenger(curve, &[(k, p), (N - k, p)]).is_infinity());
+    }
+}
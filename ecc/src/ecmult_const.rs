@@ -0,0 +1,282 @@
+/* Constant-time scalar multiplication on secp256k1 for secret scalars (private keys and ECDSA nonces), in the spirit of
+*  bitcoin-core/secp256k1's ecmult_const_impl.h and ecmult_gen_impl.h.
+*
+*  The variable-time code in ecmult.rs skips zero digits and its Jacobian formulas branch on special cases, so its running
+*  time depends on the scalar. Here every step does the same work whatever the scalar is:
+*
+*  - the scalar is cut into 64 fixed 4-bit windows, zero windows included;
+*  - table entries are fetched with a constant-time lookup that reads every entry and keeps the wanted one with
+*    S256Field::cmov, so neither branches nor memory accesses depend on the digit;
+*  - points are added with the complete formulas for a = 0 in homogeneous projective coordinates from Renes, Costello and
+*    Batina, "Complete addition formulas for prime order elliptic curves" (algorithms 7 and 9). They are correct for
+*    every pair of inputs, including the point at infinity (0 : 1 : 0) and P + P, so there are no special cases to test.
+*
+*  Only the final conversion to affine coordinates looks at the result, to return infinity for a zero scalar.
+*/
+
+use crate::ecmult::{self, G_WINDOW};
+use crate::field_5x52::S256Field;
+use crate::s256;
+use crate::scalar::Scalar;
+use crate::{Coordinates, Field, Point};
+
+// 3*b for b = 7
+const B3: S256Field = S256Field::from_u256_unchecked(crate::u256::U256::from_u64(21));
+// Fixed window of the generic multiplication: 64 windows over a table of 0..15 multiples
+const WINDOW: u32 = 4;
+
+// (X : Y : Z) stands for the affine point (X/Z, Y/Z); the point at infinity is (0 : 1 : 0)
+#[derive(Clone, Copy, Debug)]
+struct ProjectivePoint {
+    x: S256Field,
+    y: S256Field,
+    z: S256Field,
+}
+
+impl ProjectivePoint {
+    const INFINITY: ProjectivePoint = ProjectivePoint { x: S256Field::ZERO, y: S256Field::ONE, z: S256Field::ZERO };
+
+    fn from_affine(point: &Point<'_, S256Field>) -> Self {
+        match point.coordinates {
+            Coordinates::Infinity => ProjectivePoint::INFINITY,
+            Coordinates::Affine { x, y } => ProjectivePoint { x, y, z: S256Field::ONE },
+        }
+    }
+
+    fn to_affine(self) -> Point<'static, S256Field> {
+        match self.z.inverse() {
+            Ok(z_inv) => Point { coordinates: Coordinates::Affine { x: self.x * z_inv, y: self.y * z_inv }, curve: s256::curve() },
+            Err(_) => s256::curve().infinity(),
+        }
+    }
+
+    fn cmov(&mut self, other: &ProjectivePoint, flag: bool) {
+        self.x.cmov(&other.x, flag);
+        self.y.cmov(&other.y, flag);
+        self.z.cmov(&other.z, flag);
+    }
+
+    // Algorithm 7: complete addition for a = 0, 12M + 2 multiplications by b3
+    fn add(&self, other: &ProjectivePoint) -> ProjectivePoint {
+        let (x1, y1, z1) = (self.x, self.y, self.z);
+        let (x2, y2, z2) = (other.x, other.y, other.z);
+        let mut t0 = x1 * x2;
+        let mut t1 = y1 * y2;
+        let mut t2 = z1 * z2;
+        let mut t3 = (x1 + y1) * (x2 + y2);
+        let mut t4 = t0 + t1;
+        t3 = t3 - t4;
+        t4 = (y1 + z1) * (y2 + z2);
+        t4 = t4 - (t1 + t2);
+        let mut x3 = (x1 + z1) * (x2 + z2);
+        let mut y3 = x3 - (t0 + t2);
+        x3 = t0 + t0;
+        t0 = x3 + t0;
+        t2 = B3 * t2;
+        let mut z3 = t1 + t2;
+        t1 = t1 - t2;
+        y3 = B3 * y3;
+        x3 = t4 * y3;
+        t2 = t3 * t1;
+        x3 = t2 - x3;
+        y3 = y3 * t0;
+        t1 = t1 * z3;
+        y3 = t1 + y3;
+        t0 = t0 * t3;
+        z3 = z3 * t4;
+        z3 = z3 + t0;
+        ProjectivePoint { x: x3, y: y3, z: z3 }
+    }
+
+    // Algorithm 9: doubling for a = 0, 6M + 2S + 1 multiplication by b3
+    fn double(&self) -> ProjectivePoint {
+        let (x, y, z) = (self.x, self.y, self.z);
+        let mut t0 = y.square();
+        let mut z3 = t0 + t0;
+        z3 = z3 + z3;
+        z3 = z3 + z3;
+        let mut t1 = y * z;
+        let mut t2 = z.square();
+        t2 = B3 * t2;
+        let mut x3 = t2 * z3;
+        let mut y3 = t0 + t2;
+        z3 = t1 * z3;
+        t1 = t2 + t2;
+        t2 = t1 + t2;
+        t0 = t0 - t2;
+        y3 = t0 * y3;
+        y3 = x3 + y3;
+        t1 = x * y;
+        x3 = t0 * t1;
+        x3 = x3 + x3;
+        ProjectivePoint { x: x3, y: y3, z: z3 }
+    }
+}
+
+// table[index] read without branching on index or touching memory that depends on it
+fn lookup(table: &[ProjectivePoint], index: usize) -> ProjectivePoint {
+    let mut result = table[0];
+    for (j, entry) in table.iter().enumerate().skip(1) {
+        result.cmov(entry, j == index);
+    }
+    result
+}
+
+pub(crate) fn mul_const(point: &Point<'_, S256Field>, k: &Scalar) -> Point<'static, S256Field> {
+    // table[j] = j*P
+    let base = ProjectivePoint::from_affine(point);
+    let mut table = [ProjectivePoint::INFINITY; 1 << WINDOW];
+    for j in 1..table.len() {
+        table[j] = table[j - 1].add(&base);
+    }
+
+    let k = k.to_u256();
+    let mut result = ProjectivePoint::INFINITY;
+    for i in (0..256 / WINDOW).rev() {
+        for _ in 0..WINDOW {
+            result = result.double();
+        }
+        let digit = ecmult::bits_at(&k, i * WINDOW, WINDOW) as usize;
+        result = result.add(&lookup(&table, digit));
+    }
+    result.to_affine()
+}
+
+// k*G over the generator table of ecmult.rs: one constant-time row lookup and one complete addition per 4-bit window
+pub(crate) fn mul_generator_const(k: &Scalar) -> Point<'static, S256Field> {
+    let k = k.to_u256();
+    let mut result = ProjectivePoint::INFINITY;
+    for (i, row) in ecmult::generator_table().iter().enumerate() {
+        let digit = ecmult::bits_at(&k, i as u32 * G_WINDOW, G_WINDOW) as usize;
+        // Digit 0 keeps the point at infinity, digit j picks row[j - 1]
+        let mut entry = ProjectivePoint::INFINITY;
+        for (j, point) in row.iter().enumerate() {
+            entry.cmov(&ProjectivePoint::from_affine(point), digit == j + 1);
+        }
+        result = result.add(&entry);
+    }
+    result.to_affine()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::s256::N;
+    use crate::u256::U256;
+    use std::time::Instant;
+
+    struct XorShift(u64);
+
+    impl XorShift {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn scalar(&mut self) -> Scalar {
+            Scalar::from_be_bytes_reduced(&U256::from_limbs([self.next(), self.next(), self.next(), self.next()]).to_be_bytes())
+        }
+    }
+
+    #[test]
+    fn test_matches_variable_time() {
+        let g = s256::curve().generator().unwrap();
+        let mut rng = XorShift(0x853c49e6748fea9b);
+        let mut scalars = vec![Scalar::ZERO, Scalar::ONE, Scalar::from_u64(16), -Scalar::ONE];
+        for _ in 0..4 {
+            scalars.push(rng.scalar());
+        }
+        for k in scalars {
+            let expected = g.rmul_u256(&k.to_u256());
+            assert_eq!(mul_generator_const(&k), expected);
+            assert_eq!(mul_const(&g, &k), expected);
+        }
+        // (N - 1)*G + G goes through P + (-P) and an infinity input without any special-casing
+        let minus_g = mul_const(&g, &-Scalar::ONE);
+        let sum = ProjectivePoint::from_affine(&minus_g).add(&ProjectivePoint::from_affine(&g));
+        assert!(sum.to_affine().is_infinity());
+        assert!(mul_const(&s256::curve().infinity(), &Scalar::from_u64(5)).is_infinity());
+        assert!(g.rmul_u256(&N).is_infinity());
+    }
+
+    #[test]
+    fn test_complete_formulas() {
+        let g = ProjectivePoint::from_affine(&s256::curve().generator().unwrap());
+        let inf = ProjectivePoint::INFINITY;
+        // P + P through the addition formula gives the same as doubling
+        assert_eq!(g.add(&g).to_affine(), g.double().to_affine());
+        assert_eq!(g.add(&inf).to_affine(), g.to_affine());
+        assert_eq!(inf.add(&g).to_affine(), g.to_affine());
+        assert!(inf.add(&inf).to_affine().is_infinity());
+        assert!(inf.double().to_affine().is_infinity());
+    }
+
+    // Welch's t statistic between two sets of timings
+    fn t_statistic(a: &[f64], b: &[f64]) -> f64 {
+        let mean = |v: &[f64]| v.iter().sum::<f64>() / v.len() as f64;
+        let var = |v: &[f64], m: f64| v.iter().map(|x| (x - m) * (x - m)).sum::<f64>() / (v.len() - 1) as f64;
+        let (ma, mb) = (mean(a), mean(b));
+        (ma - mb) / (var(a, ma) / a.len() as f64 + var(b, mb) / b.len() as f64).sqrt()
+    }
+
+    /* dudect-style check (Reparaz, Balasch and Verbauwhede, "Dude, is my code constant time?"): time the operation for a
+    *  fixed scalar and for random scalars, interleaved in random order, drop the slowest measurements as interruptions,
+    *  and compare the two classes with Welch's t-test. dudect treats |t| above 10 as a definite leak. The same harness run
+    *  on the variable-time wNAF shows that it does detect one.
+    */
+    fn timing_t<M: Fn(&Scalar)>(multiply: M, samples: usize) -> f64 {
+        // Inputs are prepared up front so generating a random scalar does not disturb the measurement of one class only
+        let mut rng = XorShift(0x9e3779b97f4a7c15);
+        let inputs: Vec<(usize, Scalar)> = (0..samples)
+            .map(|_| match rng.next() & 1 {
+                0 => (0, Scalar::ONE),
+                _ => (1, rng.scalar()),
+            })
+            .collect();
+        let mut timings = [Vec::new(), Vec::new()];
+        for (class, k) in &inputs {
+            let start = Instant::now();
+            multiply(std::hint::black_box(k));
+            timings[*class].push(start.elapsed().as_nanos() as f64);
+        }
+        let mut all: Vec<f64> = timings.iter().flatten().copied().collect();
+        all.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let cutoff = all[all.len() * 9 / 10];
+        let cropped: Vec<Vec<f64>> = timings.iter().map(|v| v.iter().copied().filter(|&t| t <= cutoff).collect()).collect();
+        t_statistic(&cropped[0], &cropped[1])
+    }
+
+    /* Asserts on wall-clock timings, which CI load, other tests running in parallel or frequency scaling can disturb, so
+    *  it is not part of the default run. Run it on an otherwise idle machine with
+    *  `cargo test --release -- --ignored test_dudect`; test_matches_variable_time and test_complete_formulas are the
+    *  deterministic checks.
+    */
+    #[test]
+    #[ignore]
+    fn test_dudect() {
+        let g = s256::curve().generator().unwrap();
+        let leaky = timing_t(
+            |k| {
+                std::hint::black_box(g.rmul_u256(&k.to_u256()));
+            },
+            200,
+        );
+        assert!(leaky.abs() > 10.0, "the variable-time path should be detected, t = {}", leaky);
+        let t = timing_t(
+            |k| {
+                std::hint::black_box(mul_generator_const(k));
+            },
+            400,
+        );
+        assert!(t.abs() < 10.0, "mul_generator_const timing depends on the scalar, t = {}", t);
+        let t = timing_t(
+            |k| {
+                std::hint::black_box(mul_const(&g, k));
+            },
+            200,
+        );
+        assert!(t.abs() < 10.0, "mul_const timing depends on the scalar, t = {}", t);
+    }
+}
@@ -0,0 +1,743 @@
+/* Field elements modulo the secp256k1 prime p = 2^256 - 2^32 - 977, in the representation bitcoin-core/secp256k1 uses on
+*  64-bit platforms (src/field_5x52.h, src/field_5x52_impl.h and src/field_5x52_int128_impl.h).
+*
+*  The value is spread over five 64-bit limbs of nominally 52 bits each (48 bits for the top limb), so
+*  value = n[0] + n[1]*2^52 + n[2]*2^104 + n[3]*2^156 + n[4]*2^208. The 12 spare bits per limb allow additions to be
+*  performed without carrying or reducing ("lazy reduction"), which is where 'magnitude' and 'normalized' finally mean what
+*  they mean in field.h:
+*
+*  - magnitude m: every limb is at most 2*m times its nominal maximum, so additions grow m and a reduction brings it back to 1.
+*  - normalized: the limbs are fully carried and the value is the canonical representative in [0, p).
+*
+*  Only normalize()/normalize_weak() and the multiplication reduce. verify() mirrors SECP256K1_FE_VERIFY and checks the limb
+*  bounds implied by the magnitude after every operation; it is made of debug_assert! so release builds pay nothing for it.
+*/
+
+use crate::u256::U256;
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+// Low 52 bits of a limb
+const M52: u64 = 0xFFFFFFFFFFFFF;
+// Low 48 bits of the top limb
+const M48: u64 = 0x0FFFFFFFFFFFF;
+// 2^256 mod p = 2^32 + 977
+const R256: u64 = 0x1000003D1;
+// 2^260 mod p, the weight of a carry out of the top of a 5x52 product
+const R260: u64 = 0x1000003D10;
+// The lowest limb of p
+const P0: u64 = 0xFFFFEFFFFFC2F;
+
+// Largest magnitude any operation may produce, as in SECP256K1_FE_VERIFY_MAGNITUDE
+pub const MAX_MAGNITUDE: u32 = 32;
+// Largest input magnitude the multiplication is proven correct for
+const MAX_MUL_MAGNITUDE: u32 = 8;
+
+pub const P: U256 = U256::from_hex("fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f");
+
+#[derive(Clone, Copy)]
+pub struct S256Field {
+    n: [u64; 5],
+    magnitude: u32,
+    normalized: bool,
+}
+
+impl S256Field {
+    pub const ZERO: S256Field = S256Field { n: [0; 5], magnitude: 0, normalized: true };
+    pub const ONE: S256Field = S256Field { n: [1, 0, 0, 0, 0], magnitude: 1, normalized: true };
+
+    // Builds an element from a value that is already known to be below p, so it can be used for constants
+    pub const fn from_u256_unchecked(value: U256) -> S256Field {
+        let l = value.limbs();
+        S256Field {
+            n: [
+                l[0] & M52,
+                ((l[0] >> 52) | (l[1] << 12)) & M52,
+                ((l[1] >> 40) | (l[2] << 24)) & M52,
+                ((l[2] >> 28) | (l[3] << 36)) & M52,
+                l[3] >> 16,
+            ],
+            magnitude: 1,
+            normalized: true,
+        }
+    }
+
+    // Like secp256k1_fe_set_b32_limit: values that are not below p are rejected instead of being reduced
+    pub fn from_u256(value: U256) -> Result<S256Field, &'static str> {
+        if value >= P {
+            return Err("Value is not below the secp256k1 prime");
+        }
+        let element = S256Field::from_u256_unchecked(value);
+        element.verify();
+        Ok(element)
+    }
+
+    pub fn from_be_bytes(bytes: &[u8; 32]) -> Result<S256Field, &'static str> {
+        S256Field::from_u256(U256::from_be_bytes(bytes))
+    }
+
+    pub fn from_u64(n: u64) -> S256Field {
+        S256Field::from_u256_unchecked(U256::from_u64(n))
+    }
+
+    // The canonical value in [0, p)
+    pub fn to_u256(self) -> U256 {
+        let n = self.normalized().n;
+        U256::from_limbs([
+            n[0] | (n[1] << 52),
+            (n[1] >> 12) | (n[2] << 40),
+            (n[2] >> 24) | (n[3] << 28),
+            (n[3] >> 36) | (n[4] << 16),
+        ])
+    }
+
+    pub fn to_be_bytes(self) -> [u8; 32] {
+        self.to_u256().to_be_bytes()
+    }
+
+    pub fn magnitude(&self) -> u32 {
+        self.magnitude
+    }
+
+    pub fn is_normalized(&self) -> bool {
+        self.normalized
+    }
+
+    /* Checks the invariants implied by the magnitude, the equivalent of secp256k1_fe_impl_verify.
+    *
+    *  A limb of an element with magnitude m may be up to 2*m times its nominal maximum. A normalized element additionally has
+    *  to be below p, which can only be violated when every limb above the lowest one is saturated.
+    */
+    fn verify(&self) {
+        let d = &self.n;
+        let m = if self.normalized { 1 } else { 2 * self.magnitude as u64 };
+        debug_assert!(self.magnitude <= MAX_MAGNITUDE, "magnitude {} exceeds {}", self.magnitude, MAX_MAGNITUDE);
+        debug_assert!(!self.normalized || self.magnitude <= 1, "normalized element with magnitude {}", self.magnitude);
+        debug_assert!(d[0] <= M52 * m && d[1] <= M52 * m && d[2] <= M52 * m && d[3] <= M52 * m, "limb exceeds magnitude bound");
+        debug_assert!(d[4] <= M48 * m, "top limb exceeds magnitude bound");
+        if self.normalized && d[4] == M48 && (d[3] & d[2] & d[1]) == M52 {
+            debug_assert!(d[0] < P0, "normalized element is not below p");
+        }
+    }
+
+    /* Fully reduces the element to its canonical representative (secp256k1_fe_normalize).
+    *
+    *  The first pass folds everything above bit 256 back in using 2^256 = 2^32 + 977 (mod p) and carries the limbs. After that
+    *  the value is below 2^256 + p, so at most one more subtraction of p is needed; it is done by adding 2^256 - p and
+    *  dropping bit 256, selected without branching on the value.
+    */
+    pub fn normalize(&mut self) {
+        let [mut t0, mut t1, mut t2, mut t3, mut t4] = self.n;
+
+        // Reduce t4 at the start so there will be at most a single carry from the first pass
+        let mut x = t4 >> 48;
+        t4 &= M48;
+
+        // The first pass ensures the magnitude is 1, ...
+        t0 += x * R256;
+        t1 += t0 >> 52;
+        t0 &= M52;
+        t2 += t1 >> 52;
+        t1 &= M52;
+        let mut m = t1;
+        t3 += t2 >> 52;
+        t2 &= M52;
+        m &= t2;
+        t4 += t3 >> 52;
+        t3 &= M52;
+        m &= t3;
+
+        // ... except for a possible carry at bit 48 of t4 (i.e. bit 256 of the field element)
+        debug_assert!(t4 >> 49 == 0);
+
+        // At most a single final reduction is needed; check if the value is >= the field characteristic
+        x = (t4 >> 48) | ((t4 == M48) as u64 & (m == M52) as u64 & (t0 >= P0) as u64);
+
+        // Apply the final reduction (for constant-time behaviour, we do it always)
+        t0 += x * R256;
+        t1 += t0 >> 52;
+        t0 &= M52;
+        t2 += t1 >> 52;
+        t1 &= M52;
+        t3 += t2 >> 52;
+        t2 &= M52;
+        t4 += t3 >> 52;
+        t3 &= M52;
+
+        // If t4 didn't carry to bit 48 already, then it should have after any final reduction
+        debug_assert!(t4 >> 48 == x);
+
+        // Mask off the possible multiple of 2^256 from the final reduction
+        t4 &= M48;
+
+        self.n = [t0, t1, t2, t3, t4];
+        self.magnitude = 1;
+        self.normalized = true;
+        self.verify();
+    }
+
+    /* Brings the magnitude back to 1 without guaranteeing the value is below p (secp256k1_fe_normalize_weak).
+    *  This is all a multiplication input or a long chain of additions needs, and it is cheaper than normalize().
+    */
+    pub fn normalize_weak(&mut self) {
+        let [mut t0, mut t1, mut t2, mut t3, mut t4] = self.n;
+
+        // Reduce t4 at the start so there will be at most a single carry from the first pass
+        let x = t4 >> 48;
+        t4 &= M48;
+
+        // The first pass ensures the magnitude is 1, ...
+        t0 += x * R256;
+        t1 += t0 >> 52;
+        t0 &= M52;
+        t2 += t1 >> 52;
+        t1 &= M52;
+        t3 += t2 >> 52;
+        t2 &= M52;
+        t4 += t3 >> 52;
+        t3 &= M52;
+
+        // ... except for a possible carry at bit 48 of t4 (i.e. bit 256 of the field element)
+        debug_assert!(t4 >> 49 == 0);
+
+        self.n = [t0, t1, t2, t3, t4];
+        self.magnitude = 1;
+        self.verify();
+    }
+
+    pub fn normalized(&self) -> S256Field {
+        let mut copy = *self;
+        copy.normalize();
+        copy
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.normalized().n == [0; 5]
+    }
+
+    pub fn is_odd(&self) -> bool {
+        self.normalized().n[0] & 1 == 1
+    }
+
+    /* r = -a for an element of magnitude at most m (secp256k1_fe_negate).
+    *  Subtracting from 2*(m+1)*p keeps every limb non-negative, at the cost of a result with magnitude m + 1.
+    */
+    pub fn negate(&self, m: u32) -> S256Field {
+        debug_assert!(self.magnitude <= m, "negate bound {} below magnitude {}", m, self.magnitude);
+        let k = 2 * (m as u64 + 1);
+        let result = S256Field {
+            n: [
+                P0 * k - self.n[0],
+                M52 * k - self.n[1],
+                M52 * k - self.n[2],
+                M52 * k - self.n[3],
+                M48 * k - self.n[4],
+            ],
+            magnitude: m + 1,
+            normalized: false,
+        };
+        result.verify();
+        result
+    }
+
+    // Adds without carrying; the magnitudes simply add up (secp256k1_fe_add)
+    pub fn add_assign(&mut self, other: &S256Field) {
+        for i in 0..5 {
+            self.n[i] += other.n[i];
+        }
+        self.magnitude += other.magnitude;
+        self.normalized = false;
+        self.verify();
+    }
+
+    // Multiplies by a small integer, scaling the magnitude by the same factor (secp256k1_fe_mul_int)
+    pub fn mul_int(&mut self, factor: u32) {
+        for limb in self.n.iter_mut() {
+            *limb *= factor as u64;
+        }
+        self.magnitude *= factor;
+        self.normalized = false;
+        self.verify();
+    }
+
+    /* self = flag ? other : self without a branch on flag (secp256k1_fe_cmov), for constant-time table lookups.
+    *  The bookkeeping takes the worse of the two, since which value was picked must not show in it either.
+    */
+    pub fn cmov(&mut self, other: &S256Field, flag: bool) {
+        let mask = 0u64.wrapping_sub(std::hint::black_box(flag) as u64);
+        for i in 0..5 {
+            self.n[i] = (self.n[i] & !mask) | (other.n[i] & mask);
+        }
+        self.magnitude = self.magnitude.max(other.magnitude);
+        self.normalized &= other.normalized;
+        self.verify();
+    }
+
+    /* r = a * b with both inputs of magnitude at most 8; the result has magnitude 1 but is not normalized.
+    *
+    *  This is a port of secp256k1_fe_mul_inner. The comments use [... a b c] as shorthand for ... + a<<104 + b<<52 + c<<0, and px for
+    *  the sum of the partial products a[i]*b[x-i]. Bits above 2^260 are folded back in by multiplying with R260.
+    */
+    pub fn mul_inner(&self, other: &S256Field) -> S256Field {
+        debug_assert!(self.magnitude <= MAX_MUL_MAGNITUDE && other.magnitude <= MAX_MUL_MAGNITUDE);
+        let [a0, a1, a2, a3, a4] = self.n.map(|limb| limb as u128);
+        let b = other.n.map(|limb| limb as u128);
+        let m = M52 as u128;
+        let mut r = [0u64; 5];
+
+        let mut d: u128 = a0 * b[3] + a1 * b[2] + a2 * b[1] + a3 * b[0];
+        // [d 0 0 0] = [p3 0 0 0]
+        let mut c: u128 = a4 * b[4];
+        // [c 0 0 0 0 d 0 0 0] = [p8 0 0 0 0 p3 0 0 0]
+        d += R260 as u128 * (c as u64) as u128;
+        c >>= 64;
+        // [(c<<12) 0 0 0 0 0 d 0 0 0] = [p8 0 0 0 0 p3 0 0 0]
+        let t3 = (d & m) as u64;
+        d >>= 52;
+        // [(c<<12) 0 0 0 0 d t3 0 0 0] = [p8 0 0 0 0 p3 0 0 0]
+
+        d += a0 * b[4] + a1 * b[3] + a2 * b[2] + a3 * b[1] + a4 * b[0];
+        // [(c<<12) 0 0 0 0 d t3 0 0 0] = [p8 0 0 0 p4 p3 0 0 0]
+        d += ((R260 as u128) << 12) * (c as u64) as u128;
+        // [d t3 0 0 0] = [p8 0 0 0 p4 p3 0 0 0]
+        let mut t4 = (d & m) as u64;
+        d >>= 52;
+        // [d t4 t3 0 0 0] = [p8 0 0 0 p4 p3 0 0 0]
+        let tx = t4 >> 48;
+        t4 &= M48;
+        // [d t4+(tx<<48) t3 0 0 0] = [p8 0 0 0 p4 p3 0 0 0]
+
+        c = a0 * b[0];
+        // [d t4+(tx<<48) t3 0 0 c] = [p8 0 0 0 p4 p3 0 0 p0]
+        d += a1 * b[4] + a2 * b[3] + a3 * b[2] + a4 * b[1];
+        // [d t4+(tx<<48) t3 0 0 c] = [p8 0 0 p5 p4 p3 0 0 p0]
+        let mut u0 = (d & m) as u64;
+        d >>= 52;
+        // [d u0 t4+(tx<<48) t3 0 0 c] = [p8 0 0 p5 p4 p3 0 0 p0]
+        // [d 0 t4+(tx<<48)+(u0<<52) t3 0 0 c] = [p8 0 0 p5 p4 p3 0 0 p0]
+        u0 = (u0 << 4) | tx;
+        // [d 0 t4+(u0<<48) t3 0 0 c] = [p8 0 0 p5 p4 p3 0 0 p0]
+        c += u0 as u128 * (R260 >> 4) as u128;
+        // [d 0 t4 t3 0 0 c] = [p8 0 0 p5 p4 p3 0 0 p0]
+        r[0] = (c & m) as u64;
+        c >>= 52;
+        // [d 0 t4 t3 0 c r0] = [p8 0 0 p5 p4 p3 0 0 p0]
+
+        c += a0 * b[1] + a1 * b[0];
+        // [d 0 t4 t3 0 c r0] = [p8 0 0 p5 p4 p3 0 p1 p0]
+        d += a2 * b[4] + a3 * b[3] + a4 * b[2];
+        // [d 0 t4 t3 0 c r0] = [p8 0 p6 p5 p4 p3 0 p1 p0]
+        c += (d & m) * R260 as u128;
+        d >>= 52;
+        // [d 0 0 t4 t3 0 c r0] = [p8 0 p6 p5 p4 p3 0 p1 p0]
+        r[1] = (c & m) as u64;
+        c >>= 52;
+        // [d 0 0 t4 t3 c r1 r0] = [p8 0 p6 p5 p4 p3 0 p1 p0]
+
+        c += a0 * b[2] + a1 * b[1] + a2 * b[0];
+        // [d 0 0 t4 t3 c r1 r0] = [p8 0 p6 p5 p4 p3 p2 p1 p0]
+        d += a3 * b[4] + a4 * b[3];
+        // [d 0 0 t4 t3 c r1 r0] = [p8 p7 p6 p5 p4 p3 p2 p1 p0]
+        c += R260 as u128 * (d as u64) as u128;
+        d >>= 64;
+        // [(d<<12) 0 0 0 t4 t3 c r1 r0] = [p8 p7 p6 p5 p4 p3 p2 p1 p0]
+
+        r[2] = (c & m) as u64;
+        c >>= 52;
+        // [(d<<12) 0 0 0 t4 t3+c r2 r1 r0] = [p8 p7 p6 p5 p4 p3 p2 p1 p0]
+        c += ((R260 as u128) << 12) * (d as u64) as u128 + t3 as u128;
+        // [t4 c r2 r1 r0] = [p8 p7 p6 p5 p4 p3 p2 p1 p0]
+        r[3] = (c & m) as u64;
+        c >>= 52;
+        // [t4+c r3 r2 r1 r0] = [p8 p7 p6 p5 p4 p3 p2 p1 p0]
+        r[4] = c as u64 + t4;
+        // [r4 r3 r2 r1 r0] = [p8 p7 p6 p5 p4 p3 p2 p1 p0]
+
+        let result = S256Field { n: r, magnitude: 1, normalized: false };
+        result.verify();
+        result
+    }
+
+    pub fn sqr(&self) -> S256Field {
+        self.mul_inner(self)
+    }
+
+    // Squares n times in a row, the building block of the addition chains below
+    fn sqr_n(&self, n: u32) -> S256Field {
+        let mut result = *self;
+        for _ in 0..n {
+            result = result.sqr();
+        }
+        result
+    }
+
+    /* Computes 2^n - 1 powers of a for the blocks of ones in the exponents p - 2 and (p + 1) / 4.
+    *  Both exponents share the same prefix: 223 ones, a zero and 22 ones. The addition chain is the one used by
+    *  secp256k1_fe_inv and secp256k1_fe_sqrt: [1], [2], 3, 6, 9, 11, [22], 44, 88, 176, 220, [223]
+    */
+    fn pow_prefix(&self) -> (S256Field, S256Field) {
+        let a = self.weak();
+        let x2 = a.sqr().mul_inner(&a);
+        let x3 = x2.sqr().mul_inner(&a);
+        let x6 = x3.sqr_n(3).mul_inner(&x3);
+        let x9 = x6.sqr_n(3).mul_inner(&x3);
+        let x11 = x9.sqr_n(2).mul_inner(&x2);
+        let x22 = x11.sqr_n(11).mul_inner(&x11);
+        let x44 = x22.sqr_n(22).mul_inner(&x22);
+        let x88 = x44.sqr_n(44).mul_inner(&x44);
+        let x176 = x88.sqr_n(88).mul_inner(&x88);
+        let x220 = x176.sqr_n(44).mul_inner(&x44);
+        let x223 = x220.sqr_n(3).mul_inner(&x3);
+        // 223 ones, a zero, then 22 ones
+        (x223.sqr_n(23).mul_inner(&x22), x2)
+    }
+
+    /* Modular inverse by Fermat's little theorem, a^(p-2). The exponent is fixed, so the sequence of squarings and
+    *  multiplications does not depend on the value, which keeps it constant-time. Zero maps to zero.
+    */
+    pub fn inv(&self) -> S256Field {
+        let (prefix, x2) = self.pow_prefix();
+        let a = self.weak();
+        // p - 2 ends in ...0000 1 0 11 0 1
+        prefix.sqr_n(5).mul_inner(&a).sqr_n(3).mul_inner(&x2).sqr_n(2).mul_inner(&a)
+    }
+
+    /* Checked inverse for callers that cannot rule out zero. It uses the Fermat addition chain above, whose sequence of
+    *  operations is fixed, so it is constant-time; only the final zero check branches, and zero has no secret to leak.
+    */
+    pub fn inverse(&self) -> Result<S256Field, &'static str> {
+        if self.is_zero() {
+            return Err("Cannot invert zero");
+        }
+        Ok(self.inv())
+    }
+
+    // The same inverse through the divstep (safegcd) algorithm on the canonical value
+    pub fn inverse_safegcd(&self) -> Result<S256Field, &'static str> {
+        match crate::u256::inv_mod_divsteps(&self.to_u256(), &P) {
+            Some(inverse) if !self.is_zero() => Ok(S256Field::from_u256_unchecked(inverse)),
+            _ => Err("Cannot invert zero"),
+        }
+    }
+
+    /* Square root as in secp256k1_fe_sqrt: since p = 3 mod 4, a^((p+1)/4) is a root whenever one exists, and squaring it
+    *  back tells whether it does. None for non-residues. The root returned for a residue may be odd or even.
+    */
+    pub fn sqrt(&self) -> Option<S256Field> {
+        let (prefix, x2) = self.pow_prefix();
+        // (p + 1) / 4 ends in ...0000 11 00
+        let root = prefix.sqr_n(6).mul_inner(&x2).sqr_n(2);
+        if root.sqr() == *self {
+            Some(root)
+        } else {
+            None
+        }
+    }
+
+    // A copy with magnitude small enough to be fed into a multiplication
+    fn weak(&self) -> S256Field {
+        let mut copy = *self;
+        if copy.magnitude > MAX_MUL_MAGNITUDE {
+            copy.normalize_weak();
+        }
+        copy
+    }
+}
+
+/* Operators for the generic curve code. They keep the laziness where it is safe and reduce only when a bound would be
+*  exceeded: addition weakly normalizes its operands first if the sum could pass MAX_MAGNITUDE, and multiplication does the
+*  same for inputs above the magnitude secp256k1_fe_mul accepts.
+*/
+impl Add<&S256Field> for &S256Field {
+    type Output = S256Field;
+
+    fn add(self, other: &S256Field) -> S256Field {
+        let mut lhs = *self;
+        let mut rhs = *other;
+        if lhs.magnitude + rhs.magnitude > MAX_MAGNITUDE {
+            lhs.normalize_weak();
+            rhs.normalize_weak();
+        }
+        lhs.add_assign(&rhs);
+        lhs
+    }
+}
+
+impl Neg for &S256Field {
+    type Output = S256Field;
+
+    fn neg(self) -> S256Field {
+        let mut value = *self;
+        if value.magnitude >= MAX_MAGNITUDE {
+            value.normalize_weak();
+        }
+        value.negate(value.magnitude)
+    }
+}
+
+impl Sub<&S256Field> for &S256Field {
+    type Output = S256Field;
+
+    fn sub(self, other: &S256Field) -> S256Field {
+        self + (-other)
+    }
+}
+
+impl Mul<&S256Field> for &S256Field {
+    type Output = S256Field;
+
+    fn mul(self, other: &S256Field) -> S256Field {
+        self.weak().mul_inner(&other.weak())
+    }
+}
+
+impl Div<&S256Field> for &S256Field {
+    type Output = S256Field;
+
+    fn div(self, other: &S256Field) -> S256Field {
+        match other.inverse() {
+            Ok(inverse) => self.weak().mul_inner(&inverse),
+            Err(message) => panic!("{}", message),
+        }
+    }
+}
+
+// The owned and mixed combinations all forward to the reference implementations above
+macro_rules! forward_s256_op {
+    ($op_trait:ident, $op_fn:ident) => {
+        impl $op_trait<S256Field> for S256Field {
+            type Output = S256Field;
+
+            fn $op_fn(self, other: S256Field) -> S256Field {
+                $op_trait::$op_fn(&self, &other)
+            }
+        }
+
+        impl<'b> $op_trait<&'b S256Field> for S256Field {
+            type Output = S256Field;
+
+            fn $op_fn(self, other: &'b S256Field) -> S256Field {
+                $op_trait::$op_fn(&self, other)
+            }
+        }
+
+        impl<'a> $op_trait<S256Field> for &'a S256Field {
+            type Output = S256Field;
+
+            fn $op_fn(self, other: S256Field) -> S256Field {
+                $op_trait::$op_fn(self, &other)
+            }
+        }
+    };
+}
+
+forward_s256_op!(Add, add);
+forward_s256_op!(Sub, sub);
+forward_s256_op!(Mul, mul);
+forward_s256_op!(Div, div);
+
+impl Neg for S256Field {
+    type Output = S256Field;
+
+    fn neg(self) -> S256Field {
+        -&self
+    }
+}
+
+// Two representations are equal when they reduce to the same canonical value, whatever their magnitudes
+impl PartialEq for S256Field {
+    fn eq(&self, other: &S256Field) -> bool {
+        self.normalized().n == other.normalized().n
+    }
+}
+
+impl Eq for S256Field {}
+
+impl crate::Field for S256Field {
+    fn zero() -> Self {
+        S256Field::ZERO
+    }
+
+    fn one() -> Self {
+        S256Field::ONE
+    }
+
+    fn from_u64(n: u64) -> Self {
+        S256Field::from_u64(n)
+    }
+
+    fn is_zero(&self) -> bool {
+        S256Field::is_zero(self)
+    }
+
+    fn inverse(&self) -> Result<Self, &'static str> {
+        S256Field::inverse(self)
+    }
+
+    fn square(&self) -> Self {
+        self.weak().sqr()
+    }
+}
+
+impl fmt::Debug for S256Field {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "S256Field({:#066x}, magnitude: {})", self.to_u256(), self.magnitude)
+    }
+}
+
+impl fmt::Display for S256Field {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:064x}", self.to_u256())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GX: U256 = U256::from_hex("79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798");
+    const GY: U256 = U256::from_hex("483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8");
+
+    // Deterministic pseudo-random values for comparing against the U256 reference arithmetic
+    fn samples() -> Vec<U256> {
+        let mut state: u64 = 0x9e3779b97f4a7c15;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+        let mut values = vec![U256::ZERO, U256::ONE, P - U256::ONE, P - U256::from_u64(2), GX, GY];
+        for _ in 0..40 {
+            values.push(U256::from_limbs([next(), next(), next(), next()]) % P);
+        }
+        values
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        for value in samples() {
+            assert_eq!(S256Field::from_u256(value).unwrap().to_u256(), value);
+        }
+        assert!(S256Field::from_u256(P).is_err());
+    }
+
+    #[test]
+    fn test_mul_matches_reference() {
+        let values = samples();
+        for a in &values {
+            for b in values.iter().take(10) {
+                let product = S256Field::from_u256(*a).unwrap() * S256Field::from_u256(*b).unwrap();
+                assert_eq!(product.to_u256(), a.mul_mod(b, &P));
+            }
+        }
+    }
+
+    #[test]
+    fn test_add_sub_neg_match_reference() {
+        let values = samples();
+        for a in &values {
+            for b in values.iter().take(10) {
+                let x = S256Field::from_u256(*a).unwrap();
+                let y = S256Field::from_u256(*b).unwrap();
+                assert_eq!((x + y).to_u256(), a.add_mod(b, &P));
+                assert_eq!((x - y).to_u256(), a.sub_mod(b, &P));
+                assert_eq!((-x).to_u256(), U256::ZERO.sub_mod(a, &P));
+            }
+        }
+    }
+
+    #[test]
+    fn test_magnitude_tracking() {
+        let g = S256Field::from_u256(GX).unwrap();
+        let mut sum = g;
+        // Additions accumulate magnitude without reducing...
+        for expected in 2..=8 {
+            sum.add_assign(&g);
+            assert_eq!(sum.magnitude(), expected);
+            assert!(!sum.is_normalized());
+        }
+        // ... a multiplication accepts it and resets it to 1 ...
+        let product = sum.mul_inner(&g);
+        assert_eq!(product.magnitude(), 1);
+        assert_eq!(product.to_u256(), GX.mul_mod(&U256::from_u64(8), &P).mul_mod(&GX, &P));
+        // ... and the operators reduce weakly before passing the bound
+        let mut chain = g;
+        for _ in 0..100 {
+            chain = chain + g;
+            assert!(chain.magnitude() <= MAX_MAGNITUDE);
+        }
+        assert_eq!(chain.to_u256(), GX.mul_mod(&U256::from_u64(101), &P));
+
+        let negated = g.negate(1);
+        assert_eq!(negated.magnitude(), 2);
+        let mut weak = negated;
+        weak.normalize_weak();
+        assert_eq!(weak.magnitude(), 1);
+        assert!(!weak.is_normalized());
+        assert_eq!(weak, negated);
+        let mut full = weak;
+        full.normalize();
+        assert!(full.is_normalized());
+    }
+
+    // Only debug builds carry the verifier, like VERIFY builds of libsecp256k1
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "magnitude 33 exceeds 32")]
+    fn test_verify_rejects_excess_magnitude() {
+        let mut sum = S256Field::ONE;
+        for _ in 0..32 {
+            sum.add_assign(&S256Field::ONE);
+        }
+    }
+
+    #[test]
+    fn test_normalize_edge_cases() {
+        // p itself written with saturated limbs must normalize to zero
+        let mut p = S256Field { n: [P0, M52, M52, M52, M48], magnitude: 1, normalized: false };
+        p.normalize();
+        assert!(p.n == [0; 5]);
+        // p + 1 normalizes to one
+        let mut p_plus_one = S256Field { n: [P0 + 1, M52, M52, M52, M48], magnitude: 1, normalized: false };
+        p_plus_one.normalize();
+        assert_eq!(p_plus_one, S256Field::ONE);
+        assert!(S256Field::ZERO.negate(0).is_zero());
+    }
+
+    #[test]
+    fn test_inverse() {
+        for value in samples().into_iter().skip(1) {
+            let x = S256Field::from_u256(value).unwrap();
+            assert_eq!(x * x.inv(), S256Field::ONE);
+        }
+        assert!(S256Field::ZERO.inv().is_zero());
+        assert!(S256Field::ZERO.inverse().is_err());
+        assert!(S256Field::ZERO.inverse_safegcd().is_err());
+        let x = S256Field::from_u256(GX).unwrap();
+        assert_eq!(x.inverse().unwrap(), x.inverse_safegcd().unwrap());
+    }
+
+    #[test]
+    fn test_sqrt() {
+        for value in samples() {
+            let x = S256Field::from_u256(value).unwrap();
+            let square = x.sqr();
+            let root = square.sqrt().unwrap();
+            assert!(root == x || root == -x);
+            // -1 is not a square for p = 3 mod 4, so exactly one of x and -x has a root
+            if !x.is_zero() {
+                assert!(x.sqrt().is_some() != (-x).sqrt().is_some());
+            }
+        }
+        assert_eq!(S256Field::from_u64(7).sqrt(), None);
+        assert_eq!(S256Field::from_u64(4).sqrt().map(|r| r.sqr()), Some(S256Field::from_u64(4)));
+    }
+
+    #[test]
+    fn test_generator_on_curve() {
+        let x = S256Field::from_u256(GX).unwrap();
+        let y = S256Field::from_u256(GY).unwrap();
+        assert_eq!(y.sqr(), x.sqr() * x + S256Field::from_u64(7));
+    }
+}
@@ -0,0 +1,129 @@
+/* The GLV endomorphism of secp256k1 (Gallant, Lambert and Vanstone), as used by bitcoin-core/secp256k1 (src/scalar_impl.h,
+*  secp256k1_scalar_split_lambda, and src/group_impl.h, secp256k1_ge_mul_lambda). Compiled only with the `endomorphism`
+*  feature.
+*
+*  beta is a cube root of unity modulo p and lambda one modulo N, paired so that lambda*(x, y) = (beta*x, y) for every
+*  point: multiplying by lambda costs one field multiplication. Any scalar k can be split as k = k1 + k2*lambda (mod N)
+*  with |k1| and |k2| below 2^128, so k*P = k1*P + k2*(lambda*P) is a sum of two 128-bit multiplications. Walked
+*  together by Strauss' method, that takes 128 doublings instead of 256.
+*
+*  The split uses precomputed g1 = round(2^384 * b2 / N) and g2 = round(2^384 * -b1 / N), where (a1, b1) and (a2, b2)
+*  are a short basis of the lattice {(x, y) : x + y*lambda = 0 mod N}; see the derivation in scalar_impl.h.
+*/
+
+use crate::ecmult;
+use crate::field_5x52::S256Field;
+use crate::s256;
+use crate::scalar::Scalar;
+use crate::u256::U256;
+use crate::{Coordinates, Point};
+
+pub const BETA: S256Field =
+    S256Field::from_u256_unchecked(U256::from_hex("7ae96a2b657c07106e64479eac3434e99cf0497512f58995c1396c28719501ee"));
+pub const LAMBDA: U256 = U256::from_hex("5363ad4cc05c30e0a5261c028812645a122e22ea20816678df02967c1b23bd72");
+
+const MINUS_LAMBDA: U256 = U256::from_hex("ac9c52b33fa3cf1f5ad9e3fd77ed9ba4a880b9fc8ec739c2e0cfc810b51283cf");
+const MINUS_B1: U256 = U256::from_hex("00000000000000000000000000000000e4437ed6010e88286f547fa90abfe4c3");
+const MINUS_B2: U256 = U256::from_hex("fffffffffffffffffffffffffffffffe8a280ac50774346dd765cda83db1562c");
+const G1: U256 = U256::from_hex("3086d221a7d46bcde86c90e49284eb153daa8a1471e8ca7fe893209a45dbb031");
+const G2: U256 = U256::from_hex("e4437ed6010e88286f547fa90abfe4c4221208ac9df506c61571b4ae8ac47f71");
+
+// round(k * g / 2^384), as secp256k1_scalar_mul_shift_var with shift 384
+fn mul_shift_384(k: &U256, g: &U256) -> Scalar {
+    let (_, high) = k.widening_mul(g).split();
+    let rounding = (high >> 127).low_u64() & 1;
+    let result = (high >> 128) + U256::from_u64(rounding);
+    Scalar::from_u256(result).expect("a 129-bit value is below N")
+}
+
+// (k1, k2) with k = k1 + k2*lambda (mod N); both are within 2^128 of zero, i.e. below 2^128 or above N - 2^128
+pub fn split(k: &Scalar) -> (Scalar, Scalar) {
+    let c1 = mul_shift_384(&k.to_u256(), &G1);
+    let c2 = mul_shift_384(&k.to_u256(), &G2);
+    let minus_b1 = Scalar::from_u256(MINUS_B1).unwrap();
+    let minus_b2 = Scalar::from_u256(MINUS_B2).unwrap();
+    let k2 = c1 * minus_b1 + c2 * minus_b2;
+    let k1 = k2 * Scalar::from_u256(MINUS_LAMBDA).unwrap() + k;
+    (k1, k2)
+}
+
+// lambda*P = (beta*x, y)
+pub fn mul_lambda(point: &Point<'static, S256Field>) -> Point<'static, S256Field> {
+    match &point.coordinates {
+        Coordinates::Infinity => point.clone(),
+        Coordinates::Affine { x, y } => Point { coordinates: Coordinates::Affine { x: *x * BETA, y: *y }, curve: point.curve },
+    }
+}
+
+/* Replaces k*P by two terms with scalars below 2^128. A "negative" half (above N/2) is negated together with its point,
+*  so k1*P = (N - k1)*(-P) and the scalar that is actually multiplied is short.
+*/
+pub fn split_term(k: &Scalar, point: &Point<'static, S256Field>) -> [(U256, Point<'static, S256Field>); 2] {
+    let (k1, k2) = split(k);
+    let p1 = point.clone();
+    let p2 = mul_lambda(point);
+    let half = |k: Scalar, p: Point<'static, S256Field>| if k.is_high() { ((-k).to_u256(), p.neg()) } else { (k.to_u256(), p) };
+    [half(k1, p1), half(k2, p2)]
+}
+
+// k*P for a public scalar through the endomorphism; variable time like the rest of ecmult.rs
+pub fn mul(point: &Point<'static, S256Field>, k: &Scalar) -> Point<'static, S256Field> {
+    let terms = split_term(k, point);
+    let refs: Vec<(U256, &Point<S256Field>)> = terms.iter().map(|(k, p)| (*k, p)).collect();
+    ecmult::multi_mul_strauss(s256::curve(), &refs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::s256::N;
+
+    fn samples() -> Vec<Scalar> {
+        let mut state: u64 = 0xda3e39cb94b95bdb;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+        let mut values = vec![Scalar::ZERO, Scalar::ONE, -Scalar::ONE, Scalar::from_u256(LAMBDA).unwrap()];
+        for _ in 0..20 {
+            values.push(Scalar::from_be_bytes_reduced(&U256::from_limbs([next(), next(), next(), next()]).to_be_bytes()));
+        }
+        values
+    }
+
+    #[test]
+    fn test_constants() {
+        let lambda = Scalar::from_u256(LAMBDA).unwrap();
+        assert_eq!(lambda * lambda * lambda, Scalar::ONE);
+        assert_eq!(BETA * BETA * BETA, S256Field::ONE);
+        assert_eq!(MINUS_LAMBDA + LAMBDA, N);
+        let g = s256::curve().generator().unwrap();
+        assert_eq!(mul_lambda(&g), g.rmul_u256(&LAMBDA));
+    }
+
+    #[test]
+    fn test_split() {
+        let lambda = Scalar::from_u256(LAMBDA).unwrap();
+        for k in samples() {
+            let (k1, k2) = split(&k);
+            assert_eq!(k1 + k2 * lambda, k);
+            for half in [k1, k2] {
+                let short = if half.is_high() { -half } else { half };
+                assert!(short.to_u256().bits() <= 128, "{} is not a short half", half);
+            }
+        }
+    }
+
+    #[test]
+    fn test_mul_matches_ladder() {
+        let g = s256::curve().generator().unwrap();
+        let p = ecmult::mul_generator(&U256::from_u64(0xabcdef));
+        for k in samples() {
+            assert_eq!(mul(&g, &k), ecmult::mul_double_and_add(&g, &k.to_u256()));
+            assert_eq!(mul(&p, &k), ecmult::mul_double_and_add(&p, &k.to_u256()));
+        }
+        assert!(mul(&s256::curve().infinity(), &Scalar::from_u64(3)).is_infinity());
+    }
+}
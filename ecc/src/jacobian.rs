@@ -0,0 +1,291 @@
+/* Points in Jacobian coordinates: (X, Y, Z) stands for the affine point (X/Z^2, Y/Z^3), and Z = 0 for the point at
+*  infinity. Adding and doubling then only need multiplications; the single field inversion is deferred to to_affine(),
+*  which is what makes scalar multiplication over a 256-bit field affordable.
+*
+*  The formulas are the ones from the Explicit-Formulas Database (https://hyperelliptic.org/EFD/g1p/auto-shortw-jacobian.html):
+*  add-2007-bl for Jacobian + Jacobian, madd-2007-bl for Jacobian + affine, dbl-2009-l for doubling when a = 0 (secp256k1
+*  and the book's F223 curve) and dbl-2007-bl for any other a. The add functions check for the cases those formulas do not
+*  cover themselves (an input at infinity, P + P and P + (-P)), so they are correct for every pair of inputs. Like
+*  Point::add they expect both inputs on the same curve; being on the hot path, they only assert it in debug builds.
+*/
+
+use crate::{Coordinates, Curve, Field, Point};
+
+#[derive(Debug, Clone)]
+pub struct JacobianPoint<'c, F: Field> {
+    x: F,
+    y: F,
+    z: F,
+    curve: &'c Curve<F>,
+}
+
+impl<'c, F: Field> JacobianPoint<'c, F> {
+    pub fn infinity(curve: &'c Curve<F>) -> Self {
+        JacobianPoint { x: F::one(), y: F::one(), z: F::zero(), curve }
+    }
+
+    pub fn from_affine(point: &Point<'c, F>) -> Self {
+        match &point.coordinates {
+            Coordinates::Infinity => JacobianPoint::infinity(point.curve),
+            Coordinates::Affine { x, y } => JacobianPoint { x: x.clone(), y: y.clone(), z: F::one(), curve: point.curve },
+        }
+    }
+
+    pub fn is_infinity(&self) -> bool {
+        self.z.is_zero()
+    }
+
+    // One inversion of Z: x = X/Z^2, y = Y/Z^3
+    pub fn to_affine(&self) -> Point<'c, F> {
+        let z_inv = match self.z.inverse() {
+            Ok(z_inv) => z_inv,
+            Err(_) => return self.curve.infinity(),
+        };
+        let z_inv2 = z_inv.square();
+        let x = self.x.clone() * &z_inv2;
+        let y = self.y.clone() * &(z_inv2 * &z_inv);
+        // The formulas keep the point on the curve, so there is nothing to re-check
+        Point { coordinates: Coordinates::Affine { x, y }, curve: self.curve }
+    }
+
+    /* Converts many points with a single field inversion instead of one per point, for precomputed tables and for
+    *  serializing many points at once. Points at infinity come back as infinity.
+    */
+    pub fn to_affine_batch(points: &[JacobianPoint<'c, F>]) -> Vec<Point<'c, F>> {
+        let z: Vec<F> = points.iter().map(|point| point.z.clone()).collect();
+        let z_inv = F::batch_inverse(&z);
+        points
+            .iter()
+            .zip(z_inv)
+            .map(|(point, z_inv)| {
+                if point.is_infinity() {
+                    return point.curve.infinity();
+                }
+                let z_inv2 = z_inv.square();
+                let x = point.x.clone() * &z_inv2;
+                let y = point.y.clone() * &(z_inv2 * &z_inv);
+                Point { coordinates: Coordinates::Affine { x, y }, curve: point.curve }
+            })
+            .collect()
+    }
+
+    pub fn neg(&self) -> Self {
+        JacobianPoint { x: self.x.clone(), y: -self.y.clone(), z: self.z.clone(), curve: self.curve }
+    }
+
+    pub fn double(&self) -> Self {
+        // Z3 = 2*Y1*Z1 is zero for the point at infinity and for points with y = 0, which double to infinity
+        if self.curve.a.is_zero() {
+            // dbl-2009-l
+            let a = self.x.square();
+            let b = self.y.square();
+            let c = b.square();
+            let d = F::from_u64(2) * ((self.x.clone() + &b).square() - &a - &c);
+            let e = F::from_u64(3) * &a;
+            let x3 = e.square() - F::from_u64(2) * &d;
+            let y3 = e * (d - &x3) - F::from_u64(8) * &c;
+            let z3 = F::from_u64(2) * &self.y * &self.z;
+            JacobianPoint { x: x3, y: y3, z: z3, curve: self.curve }
+        } else {
+            // dbl-2007-bl
+            let xx = self.x.square();
+            let yy = self.y.square();
+            let yyyy = yy.square();
+            let zz = self.z.square();
+            let s = F::from_u64(2) * ((self.x.clone() + &yy).square() - &xx - &yyyy);
+            let m = F::from_u64(3) * &xx + self.curve.a.clone() * &zz.square();
+            let t = m.square() - F::from_u64(2) * &s;
+            let y3 = m * (s - &t) - F::from_u64(8) * &yyyy;
+            let z3 = (self.y.clone() + &self.z).square() - &yy - &zz;
+            JacobianPoint { x: t, y: y3, z: z3, curve: self.curve }
+        }
+    }
+
+    // The same test as Point::same_curve: the same Curve value, or one with equal a and b
+    fn same_curve(&self, curve: &Curve<F>) -> bool {
+        std::ptr::eq(self.curve, curve) || (self.curve.a == curve.a && self.curve.b == curve.b)
+    }
+
+    // add-2007-bl
+    pub fn add(&self, other: &JacobianPoint<'_, F>) -> Self {
+        debug_assert!(self.same_curve(other.curve), "Points are not on the same curve");
+        if self.is_infinity() {
+            return JacobianPoint { x: other.x.clone(), y: other.y.clone(), z: other.z.clone(), curve: self.curve };
+        }
+        if other.is_infinity() {
+            return self.clone();
+        }
+        let z1z1 = self.z.square();
+        let z2z2 = other.z.square();
+        let u1 = self.x.clone() * &z2z2;
+        let u2 = other.x.clone() * &z1z1;
+        let s1 = self.y.clone() * &other.z * &z2z2;
+        let s2 = other.y.clone() * &self.z * &z1z1;
+        let h = u2 - &u1;
+        let r = F::from_u64(2) * (s2 - &s1);
+        if h.is_zero() {
+            // Same x: either the same point or its negation
+            return if r.is_zero() { self.double() } else { JacobianPoint::infinity(self.curve) };
+        }
+        let i = (F::from_u64(2) * &h).square();
+        let j = h.clone() * &i;
+        let v = u1 * &i;
+        let x3 = r.square() - &j - F::from_u64(2) * &v;
+        let y3 = r * (v - &x3) - F::from_u64(2) * &s1 * &j;
+        let z3 = ((self.z.clone() + &other.z).square() - &z1z1 - &z2z2) * &h;
+        JacobianPoint { x: x3, y: y3, z: z3, curve: self.curve }
+    }
+
+    // madd-2007-bl, saving the multiplications by Z2 = 1
+    pub fn add_affine(&self, other: &Point<'_, F>) -> Self {
+        debug_assert!(self.same_curve(other.curve), "Points are not on the same curve");
+        let (x2, y2) = match &other.coordinates {
+            Coordinates::Infinity => return self.clone(),
+            Coordinates::Affine { x, y } => (x, y),
+        };
+        if self.is_infinity() {
+            return JacobianPoint { x: x2.clone(), y: y2.clone(), z: F::one(), curve: self.curve };
+        }
+        let z1z1 = self.z.square();
+        let u2 = x2.clone() * &z1z1;
+        let s2 = y2.clone() * &self.z * &z1z1;
+        let h = u2 - &self.x;
+        let r = F::from_u64(2) * (s2 - &self.y);
+        if h.is_zero() {
+            return if r.is_zero() { self.double() } else { JacobianPoint::infinity(self.curve) };
+        }
+        let hh = h.square();
+        let i = F::from_u64(4) * &hh;
+        let j = h.clone() * &i;
+        let v = self.x.clone() * &i;
+        let x3 = r.square() - &j - F::from_u64(2) * &v;
+        let y3 = r * (v - &x3) - F::from_u64(2) * &self.y * &j;
+        let z3 = (self.z.clone() + &h).square() - &z1z1 - &hh;
+        JacobianPoint { x: x3, y: y3, z: z3, curve: self.curve }
+    }
+}
+
+// Equal when they represent the same affine point: X1*Z2^2 = X2*Z1^2 and Y1*Z2^3 = Y2*Z1^3
+impl<F: Field> PartialEq for JacobianPoint<'_, F> {
+    fn eq(&self, other: &Self) -> bool {
+        if self.is_infinity() || other.is_infinity() {
+            return self.is_infinity() == other.is_infinity();
+        }
+        let z1z1 = self.z.square();
+        let z2z2 = other.z.square();
+        self.x.clone() * &z2z2 == other.x.clone() * &z1z1
+            && self.y.clone() * &(z2z2 * &other.z) == other.y.clone() * &(z1z1 * &self.z)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::u256::U256;
+    use crate::{FieldElement, PrimeField, F223};
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct F5;
+
+    impl PrimeField for F5 {
+        const MODULUS: U256 = U256::from_u64(5);
+    }
+
+    fn fe<P: PrimeField>(value: u64) -> FieldElement<P> {
+        FieldElement::new(U256::from_u64(value)).unwrap()
+    }
+
+    #[test]
+    fn test_against_affine_f223() {
+        // a = 0: every multiple of (47, 71), which has order 21
+        let curve = Curve::new(fe::<F223>(0), fe(7)).unwrap();
+        let g = curve.point(fe(47), fe(71)).unwrap();
+        let mut affine = curve.infinity();
+        let mut jacobian = JacobianPoint::infinity(&curve);
+        for _ in 0..25 {
+            assert_eq!(jacobian.to_affine(), affine);
+            assert_eq!(jacobian.double().to_affine(), affine.add(&affine).unwrap());
+            assert_eq!(jacobian.add(&jacobian).to_affine(), affine.add(&affine).unwrap());
+            assert_eq!(jacobian.add(&jacobian.neg()).to_affine(), curve.infinity());
+            affine = affine.add(&g).unwrap();
+            // Alternate the two ways of adding so both see points with Z != 1
+            jacobian = if affine.x().is_some_and(|x| x.value.is_odd()) {
+                jacobian.add_affine(&g)
+            } else {
+                jacobian.add(&JacobianPoint::from_affine(&g))
+            };
+        }
+    }
+
+    #[test]
+    fn test_against_affine_general_a() {
+        // a != 0 goes through dbl-2007-bl; (3, 0) on y^2 = x^3 + 4x + 1 over F5 has y = 0
+        let curve = Curve::new(fe::<F5>(4), fe(1)).unwrap();
+        let points = [(0, 1), (0, 4), (1, 1), (1, 4), (3, 0), (4, 1), (4, 4)];
+        for &(x1, y1) in &points {
+            let p = curve.point(fe(x1), fe(y1)).unwrap();
+            let jp = JacobianPoint::from_affine(&p).double();
+            assert_eq!(jp.to_affine(), p.add(&p).unwrap());
+            for &(x2, y2) in &points {
+                let q = curve.point(fe(x2), fe(y2)).unwrap();
+                let expected = jp.to_affine().add(&q).unwrap();
+                assert_eq!(jp.add_affine(&q).to_affine(), expected);
+                assert_eq!(jp.add(&JacobianPoint::from_affine(&q)).to_affine(), expected);
+            }
+        }
+        assert!(JacobianPoint::from_affine(&curve.point(fe(3), fe(0)).unwrap()).double().is_infinity());
+    }
+
+    #[test]
+    fn test_to_affine_batch() {
+        let curve = Curve::new(fe::<F223>(0), fe(7)).unwrap();
+        let g = curve.point(fe(47), fe(71)).unwrap();
+        // Multiples of G with different Z, which reach infinity again since G has order 21
+        let mut points = vec![JacobianPoint::infinity(&curve)];
+        for k in 1..25 {
+            points.push(points[k - 1].add_affine(&g).double().add(&JacobianPoint::from_affine(&g).neg()).add_affine(&g));
+        }
+        let batch = JacobianPoint::to_affine_batch(&points);
+        assert_eq!(batch.len(), points.len());
+        for (point, affine) in points.iter().zip(&batch) {
+            assert_eq!(&point.to_affine(), affine);
+        }
+        assert!(batch[0].is_infinity());
+        assert!(JacobianPoint::<FieldElement<F223>>::to_affine_batch(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_equality_ignores_scaling() {
+        let curve = Curve::new(fe::<F223>(0), fe(7)).unwrap();
+        let p = JacobianPoint::from_affine(&curve.point(fe(47), fe(71)).unwrap());
+        // (X*l^2, Y*l^3, Z*l) is the same point
+        let l = fe::<F223>(5);
+        let scaled = JacobianPoint { x: p.x.clone() * &l.square(), y: p.y.clone() * &l.pow(3), z: l, curve: &curve };
+        assert_eq!(p, scaled);
+        assert_ne!(p, p.neg());
+        assert_ne!(p, JacobianPoint::infinity(&curve));
+        assert_eq!(JacobianPoint::from_affine(&curve.infinity()), JacobianPoint::infinity(&curve));
+    }
+
+    // Only debug builds check the curve, the same way only they verify field magnitudes
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "Points are not on the same curve")]
+    fn test_add_rejects_other_curve() {
+        let curve = Curve::new(fe::<F223>(0), fe(7)).unwrap();
+        let other = Curve::new(fe::<F223>(0), fe(1)).unwrap();
+        let p = JacobianPoint::from_affine(&curve.point(fe(47), fe(71)).unwrap());
+        let q = JacobianPoint::from_affine(&other.point(fe(2), fe(3)).unwrap());
+        p.add(&q);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "Points are not on the same curve")]
+    fn test_add_affine_rejects_other_curve() {
+        let curve = Curve::new(fe::<F223>(0), fe(7)).unwrap();
+        let other = Curve::new(fe::<F223>(0), fe(1)).unwrap();
+        let p = JacobianPoint::from_affine(&curve.point(fe(47), fe(71)).unwrap());
+        p.add_affine(&other.point(fe(2), fe(3)).unwrap());
+    }
+}
@@ -0,0 +1,977 @@
+
+
+/***********************************************************************************************************************************************
+* Compared to Jimmy's Python code(class FieldElement, ecc.py), what the different approach to field elements is that the concept of 'magnitude'*
+* by referring to the following file : https://github.com/bitcoin-core/secp256k1/blob/master/src/field.h                                       *
+* In the context of elliptic curve cryptography and field arithmetic, "magnitude" refers to the size or order of a field element.              *
+* It is created with FieldElement objects and verified that the generated objects' magnitude and normalized are valid to verify their validity *
+* In addition, it represents the number of bits required to represent the element in binary form.                                              *
+************************************************************************************************************************************************/
+
+pub mod address;
+pub mod base58;
+pub mod ecdsa;
+pub mod ecmult;
+pub mod ecmult_const;
+pub mod field_5x52;
+#[cfg(feature = "endomorphism")]
+pub mod glv;
+pub mod hash;
+pub mod jacobian;
+pub mod s256;
+pub mod scalar;
+pub mod u256;
+
+use std::fmt;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+use u256::U256;
+
+/* The use of 'magnitude', 'normalized' in the SECP256K1_FE_VERIFY_FIELDS macro appears to be necessary to track the size of the corresponding 
+*  field element, allowing the program to easily check the size of the field element and validate it.
+*
+*  When the rust code is translated in response to the object-oriented concept of the field element class from Jimmy Song's Python code as follows, 
+*  the method code line can be reduced through the traces function that can implement 'polymorphism' in Rust as follows.
+*  
+*  Detailed method modifications are as follows.
+*  The __init__ method initializes a structure, which can be initialized by invoking a new function or other static method when creating a structure.
+*  The __repr__ method works similar to Rust's Debug trait, which allows you to output structures for debug purposes using println!, {:?} macro.
+*  The __eq__ method can be compared by implementing the PartialEq trait of Rust, which allows you to compare whether two values are equivalent.
+*  The __ne___ method is automatically provided when implementing the PartialEq trait.
+*
+*  The prime is no longer stored in every element. It is an associated constant of a marker type implementing PrimeField, so
+*  FieldElement<F223> and FieldElement<F19> are different Rust types and mixing them is rejected by the compiler instead of
+*  being re-checked at runtime on every operation.
+*/
+pub trait PrimeField: Debug + Clone + Copy + PartialEq + Eq {
+    const MODULUS: U256;
+}
+
+// Declares a marker type for a prime field, e.g. `prime_field!(F223, U256::from_u64(223));`
+macro_rules! prime_field {
+    ($name:ident, $modulus:expr) => {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub struct $name;
+
+        impl PrimeField for $name {
+            const MODULUS: U256 = $modulus;
+        }
+    };
+}
+
+// The field used throughout chapters 2 and 3 of the book
+prime_field!(F223, U256::from_u64(223));
+
+/* Arithmetic every coordinate type has to provide so Point can be written once for all of them.
+*  The operator supertraits take the right-hand side by value or by reference, which is enough for the curve formulas
+*  without cloning every operand.
+*/
+pub trait Field:
+    Sized
+    + Clone
+    + PartialEq
+    + Debug
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+    + for<'a> Add<&'a Self, Output = Self>
+    + for<'a> Sub<&'a Self, Output = Self>
+    + for<'a> Mul<&'a Self, Output = Self>
+    + for<'a> Div<&'a Self, Output = Self>
+{
+    fn zero() -> Self;
+    fn one() -> Self;
+    // Reduces a small integer into the field, used for the constants in the curve formulas
+    fn from_u64(n: u64) -> Self;
+    fn is_zero(&self) -> bool;
+    // Fails for zero, which has no inverse
+    fn inverse(&self) -> Result<Self, &'static str>;
+
+    fn square(&self) -> Self {
+        self.clone() * self
+    }
+
+    /* Inverts every element with a single inversion (Montgomery's trick): invert the product of all elements, then peel
+    *  the individual inverses off with two multiplications each. Zeros have no inverse; they are skipped and come
+    *  back as zero, so one point at infinity does not spoil a whole batch.
+    */
+    fn batch_inverse(elements: &[Self]) -> Vec<Self> {
+        // prefix[i] is the product of the non-zero elements among elements[..=i]
+        let mut prefix = Vec::with_capacity(elements.len());
+        let mut product = Self::one();
+        for element in elements {
+            if !element.is_zero() {
+                product = product * element;
+            }
+            prefix.push(product.clone());
+        }
+
+        let mut inverse = product.inverse().expect("a product of non-zero elements is non-zero");
+        let mut result = vec![Self::zero(); elements.len()];
+        for i in (0..elements.len()).rev() {
+            if elements[i].is_zero() {
+                continue;
+            }
+            // inverse is currently 1 / prefix[i]
+            result[i] = if i == 0 { inverse.clone() } else { inverse.clone() * &prefix[i - 1] };
+            inverse = inverse * &elements[i];
+        }
+        result
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct FieldElement<F: PrimeField> {
+    value: U256,
+    field: PhantomData<F>,
+}
+
+/* In Jimmy Song's book, he helped with a mathematical understanding of prime parameters by explaining the concept of order, 
+*  and here the prime is carried by the type parameter F instead of a runtime field.
+*  
+*  The secp256k1_fe structure is declared in the field.h file of the secp256k1 library and contains definitions for the FieldElement     
+*/
+impl<F: PrimeField> FieldElement<F> {
+    pub fn new(value: U256) -> Result<Self, &'static str> {
+        // Check if the value is valid (U256 is unsigned, so a value can never be negative)
+        if value >= F::MODULUS {
+            return Err("Invalid value for this field");
+        }
+        // Create and return a new FieldElement object
+        Ok(FieldElement {
+            value,
+            field: PhantomData,
+        })
+    }
+
+    // Returns the prime of the field this element belongs to
+    pub fn modulus() -> U256 {
+        F::MODULUS
+    }
+
+    /* Like the book's __pow__, the exponent may be negative and is reduced modulo p - 1 first (a^(p-1) = 1 for a != 0),
+    *  so `a.pow(-1)` is the inverse. The result can be used directly in expressions such as `s.pow(2) - 2 * x`.
+    *  A negative power of zero panics like division by zero does.
+    */
+    pub fn pow(&self, exp: i64) -> FieldElement<F> {
+        let magnitude = U256::from_u64(exp.unsigned_abs());
+        if exp >= 0 {
+            return self.pow_u256(&magnitude);
+        }
+        if self.value.is_zero() {
+            panic!("Cannot invert zero");
+        }
+        // a^-e = a^((p-1) - e mod (p-1))
+        let order = F::MODULUS - U256::ONE;
+        let reduced = magnitude.div_rem(&order).1;
+        self.pow_u256(&order.sub_mod(&reduced, &order))
+    }
+
+    // Square-and-multiply with a full 256-bit exponent such as (p+1)/4, reducing after every step so nothing overflows
+    pub fn pow_u256(&self, exp: &U256) -> FieldElement<F> {
+        // 0^e is 0 for every e > 0; reducing the exponent first would wrongly turn 0^(p-1) into 0^0 = 1
+        if self.value.is_zero() {
+            return if exp.is_zero() { FieldElement::one() } else { self.clone() };
+        }
+        let reduced = exp.div_rem(&(F::MODULUS - U256::ONE)).1;
+        FieldElement::from_reduced(self.value.pow_mod(&reduced, &F::MODULUS))
+    }
+
+    pub fn checked_div(&self, other: &FieldElement<F>) -> Result<FieldElement<F>, &'static str> {
+        // Perform finite-body division operation by multiplying with the inverse, which fails for zero
+        let inverse = other.inverse()?;
+        Ok(self * inverse)
+    }
+
+    /* Multiplicative inverse with the divstep (safegcd) algorithm. Zero has no inverse and is reported as an error
+    *  instead of producing a wrong element. Like the rest of FieldElement this is not constant-time; for secrets over the
+    *  secp256k1 prime use S256Field::inverse (field_5x52.rs).
+    */
+    pub fn inverse(&self) -> Result<FieldElement<F>, &'static str> {
+        // divsteps need an odd modulus; the only even prime is 2, where every non-zero element is its own inverse
+        if F::MODULUS == U256::from_u64(2) {
+            return self.inverse_fermat();
+        }
+        match u256::inv_mod_divsteps(&self.value, &F::MODULUS) {
+            Some(inverse) if !self.value.is_zero() => Ok(FieldElement::from_reduced(inverse)),
+            _ => Err("Cannot invert zero"),
+        }
+    }
+
+    // The book's inverse, a^(p-2) by Fermat's little theorem. A few times slower than divsteps, and just as variable-time.
+    pub fn inverse_fermat(&self) -> Result<FieldElement<F>, &'static str> {
+        if self.value.is_zero() {
+            return Err("Cannot invert zero");
+        }
+        let exp = F::MODULUS - U256::from_u64(2);
+        Ok(FieldElement::from_reduced(self.value.pow_mod(&exp, &F::MODULUS)))
+    }
+
+    pub fn rmul(&self, coefficient: u64) -> FieldElement<F> {
+        // Perform multiplication operation with the coefficient, reduced first so it is a valid field value
+        let coefficient = U256::from_u64(coefficient).div_rem(&F::MODULUS).1;
+        FieldElement::from_reduced(self.value.mul_mod(&coefficient, &F::MODULUS))
+    }
+
+    /* Legendre symbol by Euler's criterion: a^((p-1)/2) is 1 for a non-zero square, p - 1 for a non-square and 0 for zero.
+    *  Returns 1, -1 or 0 accordingly.
+    */
+    pub fn legendre(&self) -> i8 {
+        if self.value.is_zero() {
+            return 0;
+        }
+        let exp = (F::MODULUS - U256::ONE) >> 1;
+        if self.value.pow_mod(&exp, &F::MODULUS) == U256::ONE {
+            1
+        } else {
+            -1
+        }
+    }
+
+    pub fn is_square(&self) -> bool {
+        self.legendre() != -1
+    }
+
+    /* Square root, or None when the element is not a quadratic residue. Which of the two roots is returned is unspecified;
+    *  the other one is its negation.
+    *
+    *  For p = 3 mod 4 (which includes secp256k1's prime) the root is simply a^((p+1)/4). Every other odd prime goes through
+    *  Tonelli-Shanks: write p - 1 = q * 2^s with q odd and walk the 2-power part down using a known non-residue.
+    */
+    pub fn sqrt(&self) -> Option<FieldElement<F>> {
+        let p = F::MODULUS;
+        // In F2 every element is its own square root
+        if self.value.is_zero() || p == U256::from_u64(2) {
+            return Some(self.clone());
+        }
+        if self.legendre() != 1 {
+            return None;
+        }
+
+        if p.low_u64() & 3 == 3 {
+            return Some(self.pow_u256(&((p + U256::ONE) >> 2)));
+        }
+
+        // p - 1 = q * 2^s
+        let mut q = p - U256::ONE;
+        let mut s = 0;
+        while !q.is_odd() {
+            q = q >> 1;
+            s += 1;
+        }
+
+        // Any non-residue works; half of the field qualifies, so a linear search ends quickly
+        let mut z = FieldElement::<F>::from_u64(2);
+        while z.legendre() != -1 {
+            z = z + FieldElement::one();
+        }
+
+        let mut m = s;
+        let mut c = FieldElement::<F>::from_reduced(z.value.pow_mod(&q, &p));
+        let mut t = FieldElement::<F>::from_reduced(self.value.pow_mod(&q, &p));
+        let mut r = FieldElement::<F>::from_reduced(self.value.pow_mod(&((q + U256::ONE) >> 1), &p));
+
+        // Invariant: r^2 = a * t, and t has order dividing 2^(m-1)
+        while t != FieldElement::one() {
+            // Find the least i with t^(2^i) = 1
+            let mut i = 0;
+            let mut t_pow = t.clone();
+            while t_pow != FieldElement::one() {
+                t_pow = t_pow.square();
+                i += 1;
+            }
+
+            let mut b = c;
+            for _ in 0..m - i - 1 {
+                b = b.square();
+            }
+            m = i;
+            c = b.square();
+            t = t * &c;
+            r = r * b;
+        }
+        Some(r)
+    }
+
+    // Wraps a value the arithmetic above already reduced below the modulus
+    fn from_reduced(value: U256) -> FieldElement<F> {
+        debug_assert!(value < F::MODULUS);
+        FieldElement {
+            value,
+            field: PhantomData,
+        }
+    }
+}
+
+impl<F: PrimeField> Field for FieldElement<F> {
+    fn zero() -> Self {
+        FieldElement::from_reduced(U256::ZERO)
+    }
+
+    fn one() -> Self {
+        FieldElement::from_reduced(U256::ONE.div_rem(&F::MODULUS).1)
+    }
+
+    fn from_u64(n: u64) -> Self {
+        FieldElement::from_reduced(U256::from_u64(n).div_rem(&F::MODULUS).1)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.value.is_zero()
+    }
+
+    fn inverse(&self) -> Result<Self, &'static str> {
+        FieldElement::inverse(self)
+    }
+}
+
+/* Operator overloading so the curve formulas can be written the way the book writes them (`__add__`, `__sub__`, `__mul__`,
+*  `__truediv__`, `__rmul__`). Each operator is implemented for every combination of owned and borrowed operands, so callers
+*  only clone when they actually need to keep a value around.
+*
+*  Elements of different fields no longer type-check, so addition, subtraction and multiplication cannot fail.
+*  Division by zero is the one remaining bug a caller can make; checked_div and inverse report it and `/` panics like the
+*  integer types do.
+*/
+macro_rules! impl_field_op {
+    ($op_trait:ident, $op_fn:ident, |$lhs:ident, $rhs:ident| $body:expr) => {
+        impl<'a, 'b, F: PrimeField> $op_trait<&'b FieldElement<F>> for &'a FieldElement<F> {
+            type Output = FieldElement<F>;
+
+            fn $op_fn(self, other: &'b FieldElement<F>) -> FieldElement<F> {
+                let ($lhs, $rhs) = (self, other);
+                $body
+            }
+        }
+
+        impl<F: PrimeField> $op_trait<FieldElement<F>> for FieldElement<F> {
+            type Output = FieldElement<F>;
+
+            fn $op_fn(self, other: FieldElement<F>) -> FieldElement<F> {
+                $op_trait::$op_fn(&self, &other)
+            }
+        }
+
+        impl<'b, F: PrimeField> $op_trait<&'b FieldElement<F>> for FieldElement<F> {
+            type Output = FieldElement<F>;
+
+            fn $op_fn(self, other: &'b FieldElement<F>) -> FieldElement<F> {
+                $op_trait::$op_fn(&self, other)
+            }
+        }
+
+        impl<'a, F: PrimeField> $op_trait<FieldElement<F>> for &'a FieldElement<F> {
+            type Output = FieldElement<F>;
+
+            fn $op_fn(self, other: FieldElement<F>) -> FieldElement<F> {
+                $op_trait::$op_fn(self, &other)
+            }
+        }
+    };
+}
+
+impl_field_op!(Add, add, |a, b| FieldElement::from_reduced(a.value.add_mod(&b.value, &F::MODULUS)));
+impl_field_op!(Sub, sub, |a, b| FieldElement::from_reduced(a.value.sub_mod(&b.value, &F::MODULUS)));
+impl_field_op!(Mul, mul, |a, b| FieldElement::from_reduced(a.value.mul_mod(&b.value, &F::MODULUS)));
+impl_field_op!(Div, div, |a, b| match a.checked_div(b) {
+    Ok(result) => result,
+    Err(message) => panic!("{}", message),
+});
+
+// Scalar multiplication `n * element`, the counterpart of Python's __rmul__
+impl<F: PrimeField> Mul<&FieldElement<F>> for u64 {
+    type Output = FieldElement<F>;
+
+    fn mul(self, element: &FieldElement<F>) -> FieldElement<F> {
+        element.rmul(self)
+    }
+}
+
+impl<F: PrimeField> Mul<FieldElement<F>> for u64 {
+    type Output = FieldElement<F>;
+
+    fn mul(self, element: FieldElement<F>) -> FieldElement<F> {
+        element.rmul(self)
+    }
+}
+
+// Additive inverse: p - value, keeping zero as zero
+impl<F: PrimeField> Neg for &FieldElement<F> {
+    type Output = FieldElement<F>;
+
+    fn neg(self) -> FieldElement<F> {
+        FieldElement::from_reduced(U256::ZERO.sub_mod(&self.value, &F::MODULUS))
+    }
+}
+
+impl<F: PrimeField> Neg for FieldElement<F> {
+    type Output = FieldElement<F>;
+
+    fn neg(self) -> FieldElement<F> {
+        -&self
+    }
+}
+
+/* A short Weierstrass curve y^2 = x^3 + ax + b over the field F, optionally with a generator of a subgroup of known
+*  order and cofactor. Points borrow the curve instead of carrying their own copy of a and b, so every point on the
+*  book's F223 curve, a toy curve or secp256k1 is built through the single checked constructor `curve.point(x, y)`.
+*/
+#[derive(Debug, PartialEq, Clone)]
+pub struct Curve<F: Field> {
+    a: F,
+    b: F,
+    generator: Option<(F, F)>,
+    order: Option<U256>,
+    cofactor: Option<U256>,
+}
+
+impl<F: Field> Curve<F> {
+    // Rejects singular curves (4a^3 + 27b^2 = 0), which have a cusp or a node and no usable group law
+    pub fn new(a: F, b: F) -> Result<Self, &'static str> {
+        let discriminant = F::from_u64(4) * a.square() * &a + F::from_u64(27) * b.square();
+        if discriminant.is_zero() {
+            return Err("Curve is singular");
+        }
+        Ok(Curve { a, b, generator: None, order: None, cofactor: None })
+    }
+
+    // Attaches a base point; the point itself is checked, the order and cofactor are taken as given
+    pub fn with_generator(mut self, x: F, y: F, order: U256, cofactor: U256) -> Result<Self, &'static str> {
+        self.point(x.clone(), y.clone())?;
+        if order.is_zero() || cofactor.is_zero() {
+            return Err("Order and cofactor must be non-zero");
+        }
+        self.generator = Some((x, y));
+        self.order = Some(order);
+        self.cofactor = Some(cofactor);
+        Ok(self)
+    }
+
+    pub fn contains(&self, x: &F, y: &F) -> bool {
+        // y^2 = x^3 + ax + b
+        y.square() == x.square() * x + self.a.clone() * x + &self.b
+    }
+
+    pub fn point(&self, x: F, y: F) -> Result<Point<'_, F>, &'static str> {
+        if !self.contains(&x, &y) {
+            return Err("Point is not on the curve");
+        }
+        Ok(Point { coordinates: Coordinates::Affine { x, y }, curve: self })
+    }
+
+    // The point at infinity is on every curve, so this cannot fail
+    pub fn infinity(&self) -> Point<'_, F> {
+        Point { coordinates: Coordinates::Infinity, curve: self }
+    }
+
+    pub fn generator(&self) -> Option<Point<'_, F>> {
+        let (x, y) = self.generator.clone()?;
+        Some(Point { coordinates: Coordinates::Affine { x, y }, curve: self })
+    }
+
+    pub fn order(&self) -> Option<&U256> {
+        self.order.as_ref()
+    }
+
+    pub fn cofactor(&self) -> Option<&U256> {
+        self.cofactor.as_ref()
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Coordinates<F: Field> {
+    // The identity element, which has no affine coordinates
+    Infinity,
+    Affine { x: F, y: F },
+}
+
+/* Implementation of methods in Point class does not require concepts such as magnetude and normalized, for two reasons:
+*  
+*  1. The Point class represents a point on an elliptic curve, which does not require verifying the properties of a finite element like the FieldElement struct does. 
+*     Objects in the Point struct represent coordinates on an elliptic curve, which is different from the operations defined in a finite element.
+*
+*  2. In addition, concepts such as 'magnitude' and 'normalized' are not used to deal with points on elliptic curves. 
+*     The points on the elliptic curve have their respective x and y coordinate values, and you just need to check if they satisfy the equation of the elliptic curve. 
+*     Therefore, no code is needed to verify properties such as 'magnitude' and 'normalized'.
+*
+*  Therefore, validation using concepts such as 'magnitude' and 'normalized' can be skipped in the implementation of methods in Point struct.
+*
+*  Point is generic over any coordinate type implementing Field, so a point over F223 and a point over another field are
+*  different types and cannot be added together by accident.
+*
+*  Points compare by coordinates and curve, so the point at infinity is equal to every other point at infinity on the
+*  same curve and never to an affine point, including (0, 0).
+*/
+#[derive(Debug, PartialEq, Clone)]
+pub struct Point<'c, F: Field> {
+    coordinates: Coordinates<F>,
+    curve: &'c Curve<F>,
+}
+
+impl<'c, F: Field> Point<'c, F> {
+    pub fn curve(&self) -> &'c Curve<F> {
+        self.curve
+    }
+
+    pub fn is_infinity(&self) -> bool {
+        self.coordinates == Coordinates::Infinity
+    }
+
+    // Affine coordinates, None for the point at infinity
+    pub fn x(&self) -> Option<&F> {
+        match &self.coordinates {
+            Coordinates::Affine { x, .. } => Some(x),
+            Coordinates::Infinity => None,
+        }
+    }
+
+    pub fn y(&self) -> Option<&F> {
+        match &self.coordinates {
+            Coordinates::Affine { y, .. } => Some(y),
+            Coordinates::Infinity => None,
+        }
+    }
+
+    // -P = (x, -y); infinity is its own negation
+    pub fn neg(&self) -> Point<'c, F> {
+        match &self.coordinates {
+            Coordinates::Infinity => self.clone(),
+            Coordinates::Affine { x, y } => Point { coordinates: Coordinates::Affine { x: x.clone(), y: -y.clone() }, curve: self.curve },
+        }
+    }
+
+    // Points built from the same Curve value share it, so the comparison of a and b is only a fallback
+    pub fn same_curve(&self, other: &Point<'_, F>) -> bool {
+        std::ptr::eq(self.curve, other.curve) || (self.curve.a == other.curve.a && self.curve.b == other.curve.b)
+    }
+
+    pub fn add(&self, other: &Point<'_, F>) -> Result<Point<'c, F>, &'static str> {
+        if !self.same_curve(other) {
+            return Err("Points are not on the same curve");
+        }
+
+        let (x1, y1, x2, y2) = match (&self.coordinates, &other.coordinates) {
+            (Coordinates::Infinity, _) => return Ok(Point { coordinates: other.coordinates.clone(), curve: self.curve }),
+            (_, Coordinates::Infinity) => return Ok(self.clone()),
+            (Coordinates::Affine { x: x1, y: y1 }, Coordinates::Affine { x: x2, y: y2 }) => (x1, y1, x2, y2),
+        };
+
+        // Vertical line through P and -P, or the tangent at a point with y = 0
+        if x1 == x2 && (y1 != y2 || y1.is_zero()) {
+            return Ok(self.curve.infinity());
+        }
+
+        let s = if x1 == x2 {
+            // s = (3x^2 + a) / 2y
+            (F::from_u64(3) * x1.square() + &self.curve.a) / (F::from_u64(2) * y1)
+        } else {
+            // s = (y2 - y1) / (x2 - x1)
+            (y2.clone() - y1) / (x2.clone() - x1)
+        };
+        let x3 = s.square() - x1 - x2;
+        let y3 = s * (x1.clone() - &x3) - y1;
+        self.curve.point(x3, y3)
+    }
+
+    /* Scalar multiplication with a width-5 NAF of the coefficient (see ecmult.rs). The running point is kept in Jacobian
+    *  coordinates, so only the final conversion inverts. It branches on the coefficient, so it is for public scalars only.
+    */
+    pub fn rmul_u256(&self, coefficient: &U256) -> Point<'c, F> {
+        ecmult::mul_wnaf(self, coefficient, ecmult::WNAF_WINDOW)
+    }
+
+    pub fn rmul(&self, coefficient: usize) -> Result<Point<'c, F>, &'static str> {
+        Ok(self.rmul_u256(&U256::from_u64(coefficient as u64)))
+    }
+}
+
+// Same text as the book's __repr__
+impl<F: Field + fmt::Display> fmt::Display for Point<'_, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.coordinates {
+            Coordinates::Infinity => write!(f, "Point(infinity)"),
+            Coordinates::Affine { x, y } => write!(f, "Point({},{})_{}_{}", x, y, self.curve.a, self.curve.b),
+        }
+    }
+}
+
+impl<F: PrimeField> fmt::Display for FieldElement<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "FieldElement_{}({})", F::MODULUS, self.value)
+    }
+}
+
+/* In the case of Jimmy Song's Python-written test code, invalid field values were taken into account, 
+*  but in Rust, it is not common to test for invalid values or exceptions to incorrect situations when writing test scenarios. 
+*  Instead, it is important to use valid inputs to verify that the code is working as expected Languages such as Python allow you 
+*  to handle exception situations using a variety of patterns related to exception handling, but this pattern is not applied in Rust. 
+*  Rust uses panics to handle runtime errors, which are primarily used by developers to modify or debug code.
+*/
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    prime_field!(F5, U256::from_u64(5));
+    prime_field!(F13, U256::from_u64(13));
+    prime_field!(F17, U256::from_u64(17));
+    prime_field!(F19, U256::from_u64(19));
+    // The order of the secp256k1 group, a 256-bit prime that is 1 mod 4
+    prime_field!(N256k1, U256::from_hex("fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141"));
+    // The secp256k1 prime 2^256 - 2^32 - 977
+    prime_field!(P256k1, U256::from_hex("fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f"));
+
+    // Shorthand for building test elements from small integers
+    fn fe<F: PrimeField>(value: u64) -> Result<FieldElement<F>, &'static str> {
+        FieldElement::new(U256::from_u64(value))
+    }
+
+    #[test]
+    fn test_new_valid() {
+        let field_element = fe::<F13>(5);
+        assert!(field_element.is_ok());
+        assert!(fe::<F13>(13).is_err());
+    }
+
+
+    #[test]
+    fn test_add_valid() {
+        let field_element1 = fe::<F13>(5).unwrap();
+        let field_element2 = fe::<F13>(7).unwrap();
+        let result = field_element1 + field_element2;
+        assert_eq!(result, fe(12).unwrap());
+    }
+
+    #[test]
+    fn test_sub_valid() {
+        let field_element1 = fe::<F13>(7).unwrap();
+        let field_element2 = fe::<F13>(5).unwrap();
+        let result = field_element1 - field_element2;
+        assert_eq!(result, fe(2).unwrap());
+    }
+
+    #[test]
+    fn test_mul_valid() {
+        let field_element1 = fe::<F13>(5).unwrap();
+        let field_element2 = fe::<F13>(7).unwrap();
+        let result = field_element1 * field_element2;
+        assert_eq!(result, fe(9).unwrap());
+    }
+
+    #[test]
+    fn test_pow_valid() {
+        let field_element = fe::<F13>(5).unwrap();
+        let result = field_element.pow(3);
+        assert_eq!(result, fe(8).unwrap());
+    }
+
+    // Negative exponents from the book's chapter 1 exercises
+    #[test]
+    fn test_pow_negative() {
+        assert_eq!(fe::<F13>(7).unwrap().pow(-3), fe(8).unwrap());
+        assert_eq!(fe::<F13>(7).unwrap().pow(-1), fe::<F13>(7).unwrap().inverse().unwrap());
+        assert_eq!(fe::<F13>(3).unwrap().pow(-12), FieldElement::one());
+        assert_eq!(fe::<F13>(3).unwrap().pow(i64::MIN), fe::<F13>(3).unwrap().pow_u256(&(U256::ONE << 63)).inverse().unwrap());
+    }
+
+    #[test]
+    fn test_pow_large_exponents() {
+        // Exponents larger than p - 1 wrap around instead of overflowing
+        assert_eq!(fe::<F13>(5).unwrap().pow(3 + 12 * 1_000_000_007), fe(8).unwrap());
+        assert_eq!(fe::<F13>(5).unwrap().pow_u256(&U256::MAX), fe::<F13>(5).unwrap().pow_u256(&(U256::MAX.div_rem(&U256::from_u64(12)).1)));
+        // Zero to a positive power stays zero even when the exponent is a multiple of p - 1
+        assert_eq!(fe::<F13>(0).unwrap().pow(12), FieldElement::zero());
+        assert_eq!(fe::<F13>(0).unwrap().pow(0), FieldElement::one());
+
+        // Fermat's little theorem with a 256-bit exponent
+        let p = P256k1::MODULUS;
+        let x = FieldElement::<P256k1>::new(U256::from_hex("79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798")).unwrap();
+        assert_eq!(x.pow_u256(&(p - U256::ONE)), FieldElement::one());
+        assert_eq!(x.pow_u256(&(p - U256::from_u64(2))), x.inverse().unwrap());
+        let root = x.square().pow_u256(&((p + U256::ONE) >> 2));
+        assert!(root == x || root == -x);
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot invert zero")]
+    fn test_pow_negative_zero_panics() {
+        let _ = fe::<F13>(0).unwrap().pow(-1);
+    }
+    
+    #[test]
+    fn test_truediv_valid() {
+        let field_element1 = fe::<F13>(7).unwrap();
+        let field_element2 = fe::<F13>(5).unwrap();
+        let result = field_element1.checked_div(&field_element2);
+        assert_eq!(result, Ok(fe(4).unwrap()));
+
+        // Dividing by zero is an error instead of a silently wrong element
+        let zero = fe::<F13>(0).unwrap();
+        assert_eq!(field_element1.checked_div(&zero), Err("Cannot invert zero"));
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot invert zero")]
+    fn test_div_operator_by_zero_panics() {
+        let _ = fe::<F13>(7).unwrap() / fe::<F13>(0).unwrap();
+    }
+
+    #[test]
+    fn test_inverse_variants_agree() {
+        for value in 1..223 {
+            let element = fe::<F223>(value).unwrap();
+            let inverse = element.inverse().unwrap();
+            assert_eq!(inverse, element.inverse_fermat().unwrap());
+            assert_eq!(inverse * element, FieldElement::one());
+        }
+        let x = FieldElement::<P256k1>::new(U256::from_hex("79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798")).unwrap();
+        assert_eq!(x.inverse().unwrap(), x.inverse_fermat().unwrap());
+        assert!(FieldElement::<P256k1>::zero().inverse().is_err());
+        assert!(FieldElement::<P256k1>::zero().inverse_fermat().is_err());
+    }
+
+    #[test]
+    fn test_batch_inverse() {
+        let elements: Vec<FieldElement<F223>> = [5, 0, 1, 222, 0, 100].iter().map(|&v| fe(v).unwrap()).collect();
+        let inverses = FieldElement::batch_inverse(&elements);
+        for (element, inverse) in elements.iter().zip(&inverses) {
+            match element.inverse() {
+                Ok(expected) => assert_eq!(inverse, &expected),
+                Err(_) => assert!(inverse.is_zero()),
+            }
+        }
+        assert!(FieldElement::<F223>::batch_inverse(&[]).is_empty());
+        assert_eq!(FieldElement::<F223>::batch_inverse(&[fe(0).unwrap()]), vec![fe(0).unwrap()]);
+    }
+
+    #[test]
+    fn test_rmul_valid() {
+        let field_element = fe::<F13>(5).unwrap();
+        let result = field_element.rmul(3);
+        assert_eq!(result, fe(2).unwrap());
+    }
+
+    #[test]
+    fn test_secp256k1_sized_values() {
+        // Values this large overflowed the old i32 backing immediately
+        let p_minus_one = FieldElement::<P256k1>::new(P256k1::MODULUS - U256::ONE).unwrap();
+        let one = FieldElement::<P256k1>::one();
+        assert_eq!(&p_minus_one + &one, FieldElement::zero());
+        assert_eq!(&p_minus_one * &p_minus_one, one);
+        assert_eq!((&one - &p_minus_one).value, U256::from_u64(2));
+
+        // y^2 = x^3 + 7 holds for the secp256k1 generator
+        let x = FieldElement::<P256k1>::new(U256::from_hex("79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798")).unwrap();
+        let y = FieldElement::<P256k1>::new(U256::from_hex("483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8")).unwrap();
+        assert_eq!(y.pow(2), x.pow(3) + FieldElement::from_u64(7));
+    }
+
+    #[test]
+    fn test_truediv_large_field() {
+        let a = FieldElement::<P256k1>::new(U256::from_hex("deadbeef12345")).unwrap();
+        let b = FieldElement::<P256k1>::from_u64(2018);
+        let quotient = a.checked_div(&b).unwrap();
+        assert_eq!(quotient * b, a);
+    }
+
+    #[test]
+    fn test_operators() {
+        // Examples from chapter 1 of the book
+        assert_eq!(fe::<F13>(7).unwrap() + fe(12).unwrap(), fe(6).unwrap());
+        assert_eq!(fe::<F13>(3).unwrap() * fe(12).unwrap(), fe(10).unwrap());
+        assert_eq!(fe::<F13>(3).unwrap().pow(3), fe(1).unwrap());
+        assert_eq!(fe::<F19>(2).unwrap() / fe(7).unwrap(), fe(3).unwrap());
+        assert_eq!(&fe::<F19>(6).unwrap() - &fe(13).unwrap(), fe(12).unwrap());
+        assert_eq!(3 * fe::<F13>(12).unwrap(), fe(10).unwrap());
+        assert_eq!(-fe::<F13>(5).unwrap(), fe(8).unwrap());
+        assert_eq!(-fe::<F13>(0).unwrap(), fe(0).unwrap());
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(fe::<F13>(7).unwrap().to_string(), "FieldElement_13(7)");
+    }
+
+    // Checks sqrt and legendre against a brute-force table of squares
+    fn check_sqrt_exhaustive<F: PrimeField>() {
+        let p = F::MODULUS.low_u64();
+        let squares: Vec<u64> = (0..p).map(|x| x * x % p).collect();
+        for value in 0..p {
+            let element = fe::<F>(value).unwrap();
+            match element.sqrt() {
+                Some(root) => assert_eq!(root.square(), element),
+                None => assert!(!squares.contains(&value)),
+            }
+            assert_eq!(element.is_square(), squares.contains(&value));
+            let expected = if value == 0 { 0 } else if squares.contains(&value) { 1 } else { -1 };
+            assert_eq!(element.legendre(), expected);
+        }
+    }
+
+    #[test]
+    fn test_sqrt_small_fields() {
+        // 19 and 223 are 3 mod 4; 13 (s = 2) and 17 (s = 4) exercise Tonelli-Shanks
+        check_sqrt_exhaustive::<F13>();
+        check_sqrt_exhaustive::<F17>();
+        check_sqrt_exhaustive::<F19>();
+        check_sqrt_exhaustive::<F223>();
+    }
+
+    #[test]
+    fn test_sqrt_secp256k1() {
+        // Recover the generator's y coordinate from y^2 = x^3 + 7
+        let x = FieldElement::<P256k1>::new(U256::from_hex("79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798")).unwrap();
+        let y = FieldElement::<P256k1>::new(U256::from_hex("483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8")).unwrap();
+        let root = (x.pow(3) + FieldElement::from_u64(7)).sqrt().unwrap();
+        assert!(root == y || root == -y);
+        // -1 is not a square when p = 3 mod 4
+        assert!(!(-FieldElement::<P256k1>::one()).is_square());
+        assert_eq!((-FieldElement::<P256k1>::one()).sqrt(), None);
+    }
+
+    #[test]
+    fn test_sqrt_tonelli_shanks_256_bit() {
+        let x = FieldElement::<N256k1>::new(U256::from_hex("deadbeef12345deadbeef12345")).unwrap();
+        let root = x.square().sqrt().unwrap();
+        assert!(root == x || root == -x);
+        // -1 is a square when p = 1 mod 4
+        assert!((-FieldElement::<N256k1>::one()).is_square());
+    }
+    
+    #[test]
+    fn test_point_new_valid() {
+        // Test valid point creation
+        let x = fe::<F5>(2).unwrap();
+        let y = fe::<F5>(3).unwrap();
+        let curve = Curve::new(fe(4).unwrap(), fe(3).unwrap()).unwrap();
+        let point = curve.point(x, y);
+        assert!(point.is_ok());
+    }
+    
+    #[test]
+    fn test_point_new_invalid() {
+        // Test invalid point creation (point not on the curve)
+        let x = fe::<F5>(2).unwrap();
+        let y = fe::<F5>(3).unwrap();
+        let curve = Curve::new(fe(4).unwrap(), fe(4).unwrap()).unwrap(); // Incorrect b value
+        let point = curve.point(x, y);
+        assert!(point.is_err());
+    }
+    
+    #[test]
+    fn test_point_add() {
+        // Test point addition (y^2 = x^3 + 2x + 3 over F5 is singular and now rejected, so use a curve that is not)
+        let curve = Curve::new(fe::<F5>(4).unwrap(), fe(1).unwrap()).unwrap();
+        let x1 = fe::<F5>(1).unwrap();
+        let y1 = fe::<F5>(1).unwrap();
+        let x2 = fe::<F5>(4).unwrap();
+        let y2 = fe::<F5>(1).unwrap();
+        let p1 = curve.point(x1, y1).unwrap();
+        let p2 = curve.point(x2, y2).unwrap();
+        let sum = p1.add(&p2);
+        assert!(sum.is_ok());
+        let result = sum.unwrap();
+        assert_eq!(result.x().unwrap().value, U256::from_u64(0));
+        assert_eq!(result.y().unwrap().value, U256::from_u64(4));
+    }
+    
+    #[test]
+    fn test_point_rmul() {
+        // Test scalar multiplication
+        let curve = Curve::new(fe::<F5>(4).unwrap(), fe(1).unwrap()).unwrap();
+        let x = fe::<F5>(1).unwrap();
+        let y = fe::<F5>(1).unwrap();
+        let p = curve.point(x, y).unwrap();
+        let scalar = 3;
+        let result = p.rmul(scalar);
+        assert!(result.is_ok());
+        let result_point = result.unwrap();
+        assert_eq!(result_point.x().unwrap().value, U256::from_u64(0));
+        assert_eq!(result_point.y().unwrap().value, U256::from_u64(4));
+    }
+
+    #[test]
+    fn test_point_add_f223() {
+        // Examples from chapter 3 of the book: y^2 = x^3 + 7 over F223
+        let curve = Curve::new(fe::<F223>(0).unwrap(), fe(7).unwrap()).unwrap();
+        let point = |x, y| curve.point(fe(x).unwrap(), fe(y).unwrap());
+
+        assert!(point(192, 105).is_ok());
+        assert!(point(200, 119).is_err());
+        assert_eq!(point(170, 142).unwrap().add(&point(60, 139).unwrap()).unwrap(), point(220, 181).unwrap());
+        assert_eq!(point(47, 71).unwrap().add(&point(47, 71).unwrap()).unwrap(), point(36, 111).unwrap());
+    }
+
+    #[test]
+    fn test_point_infinity() {
+        let curve = Curve::new(fe::<F223>(0).unwrap(), fe(7).unwrap()).unwrap();
+        let infinity = curve.infinity();
+        let p = curve.point(fe(47).unwrap(), fe(71).unwrap()).unwrap();
+        let minus_p = curve.point(fe(47).unwrap(), -fe::<F223>(71).unwrap()).unwrap();
+
+        assert!(infinity.is_infinity());
+        assert!(!p.is_infinity());
+        assert_eq!(infinity.x(), None);
+        assert_eq!(p.add(&infinity).unwrap(), p);
+        assert_eq!(infinity.add(&p).unwrap(), p);
+        assert_eq!(p.add(&minus_p).unwrap(), infinity);
+        assert_eq!(p.rmul(0).unwrap(), infinity);
+        // 47,71 generates a group of order 21
+        assert_eq!(p.rmul(21).unwrap(), infinity);
+        assert_eq!(p.rmul(22).unwrap(), p);
+
+        // Infinity and (0, 0) no longer collide on a curve where (0, 0) is a point
+        let curve = Curve::new(fe::<F5>(1).unwrap(), fe(0).unwrap()).unwrap();
+        let zero = curve.point(fe(0).unwrap(), fe(0).unwrap()).unwrap();
+        assert_ne!(zero, curve.infinity());
+        assert!(!zero.is_infinity());
+        assert!(zero.add(&zero).unwrap().is_infinity());
+    }
+
+    #[test]
+    fn test_point_display() {
+        let curve = Curve::new(fe::<F223>(0).unwrap(), fe(7).unwrap()).unwrap();
+        let p = curve.point(fe(192).unwrap(), fe(105).unwrap()).unwrap();
+        assert_eq!(p.to_string(), "Point(FieldElement_223(192),FieldElement_223(105))_FieldElement_223(0)_FieldElement_223(7)");
+        assert_eq!(curve.infinity().to_string(), "Point(infinity)");
+    }
+
+    #[test]
+    fn test_curve_new() {
+        // y^2 = x^3 has a cusp and y^2 = x^3 - 3x + 2 a node
+        assert_eq!(Curve::new(fe::<F223>(0).unwrap(), fe(0).unwrap()), Err("Curve is singular"));
+        assert!(Curve::new(-fe::<F223>(3).unwrap(), fe(2).unwrap()).is_err());
+        assert!(Curve::new(fe::<F223>(0).unwrap(), fe(7).unwrap()).is_ok());
+
+        // Points on different curves over the same field cannot be added
+        let curve1 = Curve::new(fe::<F223>(0).unwrap(), fe(7).unwrap()).unwrap();
+        let curve2 = Curve::new(fe::<F223>(1).unwrap(), fe(7).unwrap()).unwrap();
+        let p = curve1.point(fe(47).unwrap(), fe(71).unwrap()).unwrap();
+        assert_eq!(p.add(&curve2.infinity()), Err("Points are not on the same curve"));
+        // An identical copy of the curve is the same curve
+        let copy = curve1.clone();
+        assert_eq!(p.add(&copy.infinity()).unwrap(), p);
+    }
+
+    #[test]
+    fn test_curve_generator() {
+        let curve = Curve::new(fe::<F223>(0).unwrap(), fe(7).unwrap()).unwrap();
+        assert!(curve.generator().is_none());
+        assert!(curve.clone().with_generator(fe(200).unwrap(), fe(119).unwrap(), U256::from_u64(21), U256::ONE).is_err());
+
+        let curve = curve.with_generator(fe(47).unwrap(), fe(71).unwrap(), U256::from_u64(21), U256::ONE).unwrap();
+        let g = curve.generator().unwrap();
+        let order = curve.order().unwrap().low_u64() as usize;
+        assert_eq!(g.rmul(order).unwrap(), curve.infinity());
+        assert_eq!(curve.cofactor(), Some(&U256::ONE));
+        assert!(std::ptr::eq(g.curve(), &curve));
+
+        // secp256k1 itself goes through the same constructor
+        let curve = Curve::new(FieldElement::<P256k1>::zero(), FieldElement::from_u64(7)).unwrap();
+        let gx = FieldElement::new(U256::from_hex("79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798")).unwrap();
+        let gy = FieldElement::new(U256::from_hex("483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8")).unwrap();
+        assert!(curve.with_generator(gx, gy, N256k1::MODULUS, U256::ONE).is_ok());
+    }
+}
@@ -0,0 +1,311 @@
+/* secp256k1: y^2 = x^3 + 7 over the field of S256Field (p = 2^256 - 2^32 - 977), with the generator G of prime order N
+*  and cofactor 1, as listed in SEC 2 section 2.4.1.
+*
+*  The curve is a single static value, so every S256Point borrows the same Curve and nobody has to hand-construct a, b or
+*  G again. Because the group has prime order N and no cofactor, every point on the curve satisfies N*P = infinity and
+*  scalars can be reduced modulo N before multiplying.
+*/
+
+use crate::ecmult;
+use crate::ecmult_const;
+use crate::field_5x52::S256Field;
+#[cfg(feature = "endomorphism")]
+use crate::glv;
+use crate::scalar::Scalar;
+use crate::u256::U256;
+use crate::{Curve, Point};
+use std::fmt;
+
+pub const N: U256 = U256::from_hex("fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141");
+pub const GX: U256 = U256::from_hex("79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798");
+pub const GY: U256 = U256::from_hex("483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8");
+
+static SECP256K1: Curve<S256Field> = Curve {
+    a: S256Field::ZERO,
+    b: S256Field::from_u256_unchecked(U256::from_u64(7)),
+    generator: Some((S256Field::from_u256_unchecked(GX), S256Field::from_u256_unchecked(GY))),
+    order: Some(N),
+    cofactor: Some(U256::ONE),
+};
+
+pub fn curve() -> &'static Curve<S256Field> {
+    &SECP256K1
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct S256Point(Point<'static, S256Field>);
+
+impl S256Point {
+    // Fails when a coordinate is not below p or the point is not on the curve
+    pub fn new(x: U256, y: U256) -> Result<Self, &'static str> {
+        let x = S256Field::from_u256(x)?;
+        let y = S256Field::from_u256(y)?;
+        SECP256K1.point(x, y).map(S256Point)
+    }
+
+    pub fn infinity() -> Self {
+        S256Point(SECP256K1.infinity())
+    }
+
+    pub fn generator() -> Self {
+        S256Point(SECP256K1.generator().expect("secp256k1 has a generator"))
+    }
+
+    pub fn is_infinity(&self) -> bool {
+        self.0.is_infinity()
+    }
+
+    pub fn x(&self) -> Option<&S256Field> {
+        self.0.x()
+    }
+
+    pub fn y(&self) -> Option<&S256Field> {
+        self.0.y()
+    }
+
+    pub fn point(&self) -> &Point<'static, S256Field> {
+        &self.0
+    }
+
+    pub fn add(&self, other: &S256Point) -> S256Point {
+        // Both points borrow the one static curve, so the only error Point::add reports cannot happen
+        S256Point(self.0.add(&other.0).expect("points on secp256k1 share the curve"))
+    }
+
+    // The scalar is reduced modulo N first, so any 256-bit value is accepted
+    pub fn rmul(&self, coefficient: &U256) -> S256Point {
+        let coefficient = coefficient.div_rem(&N).1;
+        #[cfg(feature = "endomorphism")]
+        let product = glv::mul(&self.0, &Scalar::from_u256(coefficient).expect("reduced modulo N"));
+        #[cfg(not(feature = "endomorphism"))]
+        let product = self.0.rmul_u256(&coefficient);
+        S256Point(product)
+    }
+
+    // k*G through the precomputed generator table, several times faster than generator().rmul(k)
+    pub fn mul_generator(coefficient: &U256) -> S256Point {
+        S256Point(ecmult::mul_generator(&coefficient.div_rem(&N).1))
+    }
+
+    /* Sum of k_i*P_i for public scalars, e.g. u*G + v*P in signature verification. Much cheaper than separate
+    *  multiplications: Strauss' method shares the doublings, and Pippenger's takes over for large batches.
+    */
+    pub fn multi_mul(terms: &[(Scalar, S256Point)]) -> S256Point {
+        // With the endomorphism every term becomes two with 128-bit scalars, which halves the shared doublings
+        #[cfg(feature = "endomorphism")]
+        let split: Vec<(U256, Point<S256Field>)> = terms.iter().flat_map(|(k, point)| glv::split_term(k, &point.0)).collect();
+        #[cfg(feature = "endomorphism")]
+        let terms: Vec<(U256, &Point<S256Field>)> = split.iter().map(|(k, point)| (*k, point)).collect();
+        #[cfg(not(feature = "endomorphism"))]
+        let terms: Vec<(U256, &Point<S256Field>)> = terms.iter().map(|(k, point)| (k.to_u256(), &point.0)).collect();
+        S256Point(ecmult::multi_mul(&SECP256K1, &terms))
+    }
+
+    /* rmul and mul_generator take time that depends on the scalar. Secret scalars (private keys, nonces, anything a
+    *  Scalar holds) go through these two instead, which run in constant time (see ecmult_const.rs).
+    */
+    pub fn mul_secret(&self, secret: &Scalar) -> S256Point {
+        S256Point(ecmult_const::mul_const(&self.0, secret))
+    }
+
+    // The public point of a secret key, secret*G
+    pub fn from_secret(secret: &Scalar) -> S256Point {
+        S256Point(ecmult_const::mul_generator_const(secret))
+    }
+
+    /* SEC1 encoding (section 2.3.3): 0x04 || x || y uncompressed, or 0x02 / 0x03 || x compressed, where the prefix is the
+    *  parity of y. The point at infinity is the single byte 0x00, which parse() does not accept as a public key.
+    */
+    pub fn sec(&self, compressed: bool) -> Vec<u8> {
+        let (x, y) = match (self.x(), self.y()) {
+            (Some(x), Some(y)) => (x, y),
+            _ => return vec![0x00],
+        };
+        let mut sec = Vec::with_capacity(65);
+        if compressed {
+            sec.push(if y.is_odd() { 0x03 } else { 0x02 });
+            sec.extend(x.to_be_bytes());
+        } else {
+            sec.push(0x04);
+            sec.extend(x.to_be_bytes());
+            sec.extend(y.to_be_bytes());
+        }
+        sec
+    }
+
+    /* Reads a SEC1 public key the way secp256k1_ec_pubkey_parse does: 33 bytes compressed, 65 bytes uncompressed, or 65
+    *  bytes in the hybrid format with prefix 0x06 / 0x07, which carries y but also its parity, and must be consistent.
+    *  Hybrid keys are non-standard but valid in old scripts. A compressed x is decompressed with y = sqrt(x^3 + 7).
+    */
+    pub fn parse(sec: &[u8]) -> Result<S256Point, &'static str> {
+        match (sec.len(), sec.first()) {
+            (33, Some(&prefix)) if prefix == 0x02 || prefix == 0x03 => {
+                let x = S256Field::from_be_bytes(sec[1..33].try_into().unwrap())?;
+                let y_squared = x * x * x + SECP256K1.b;
+                let mut y = y_squared.sqrt().ok_or("Point is not on the curve")?;
+                if y.is_odd() != (prefix == 0x03) {
+                    y = -y;
+                }
+                SECP256K1.point(x, y).map(S256Point)
+            }
+            (65, Some(&prefix)) if prefix == 0x04 || prefix == 0x06 || prefix == 0x07 => {
+                let x = S256Field::from_be_bytes(sec[1..33].try_into().unwrap())?;
+                let y = S256Field::from_be_bytes(sec[33..65].try_into().unwrap())?;
+                if prefix != 0x04 && y.is_odd() != (prefix == 0x07) {
+                    return Err("Hybrid prefix does not match the parity of y");
+                }
+                SECP256K1.point(x, y).map(S256Point)
+            }
+            _ => Err("Invalid SEC prefix or length"),
+        }
+    }
+}
+
+// Same text as the book's S256Point.__repr__
+impl fmt::Display for S256Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.x(), self.y()) {
+            (Some(x), Some(y)) => write!(f, "S256Point({}, {})", x, y),
+            _ => write!(f, "S256Point(infinity)"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generator() {
+        let g = S256Point::generator();
+        assert_eq!(g, S256Point::new(GX, GY).unwrap());
+        assert!(g.rmul(&N).is_infinity());
+        assert!(S256Point::new(GX, GY + U256::ONE).is_err());
+        // A coordinate of p or more is not a field element at all
+        assert!(S256Point::new(crate::field_5x52::P, GY).is_err());
+    }
+
+    #[test]
+    fn test_pubpoint() {
+        // Public keys from chapter 3 of the book
+        let cases = [
+            (U256::from_u64(7),
+             "5cbdf0646e5db4eaa398f365f2ea7a0e3d419b7e0330e39ce92bddedcac4f9bc",
+             "6aebca40ba255960a3178d6d861a54dba813d0b813fde7b5a5082628087264da"),
+            (U256::from_u64(1485),
+             "c982196a7466fbbbb0e27a940b6af926c1a74d5ad07128c82824a11b5398afda",
+             "7a91f9eae64438afb9ce6448a1c133db2d8fb9254e4546b6f001637d50901f55"),
+            (U256::ONE << 128,
+             "8f68b9d2f63b5f339239c1ad981f162ee88c5678723ea3351b7b444c9ec4c0da",
+             "662a9f2dba063986de1d90c2b6be215dbbea2cfe95510bfdf23cbf79501fff82"),
+            ((U256::ONE << 240) + (U256::ONE << 31),
+             "9577ff57c8234558f293df502ca4f09cbc65a6572c842b39b366f21717945116",
+             "10b49c67fa9365ad7b90dab070be339a1daf9052373ec30ffae4f72d5e66d053"),
+        ];
+        for (secret, x, y) in cases {
+            let point = S256Point::new(U256::from_hex(x), U256::from_hex(y)).unwrap();
+            assert_eq!(S256Point::generator().rmul(&secret), point);
+            assert_eq!(S256Point::mul_generator(&secret), point);
+            assert_eq!(S256Point::from_secret(&Scalar::from_u256(secret).unwrap()), point);
+        }
+    }
+
+    #[test]
+    fn test_rmul_reduces_mod_n() {
+        let g = S256Point::generator();
+        let seven = g.rmul(&U256::from_u64(7));
+        assert_eq!(g.rmul(&(N + U256::from_u64(7))), seven);
+        assert!(g.rmul(&U256::ZERO).is_infinity());
+        // (N - 1)*G = -G
+        let minus_g = g.rmul(&(N - U256::ONE));
+        assert_eq!(minus_g.x(), g.x());
+        assert!(minus_g.add(&g).is_infinity());
+    }
+
+    #[test]
+    fn test_multi_mul() {
+        let g = S256Point::generator();
+        let p = S256Point::mul_generator(&U256::from_u64(1485));
+        let (u, v) = (Scalar::from_u64(7), Scalar::from_u64(3));
+        // 7*G + 3*(1485*G) = 4462*G
+        assert_eq!(S256Point::multi_mul(&[(u, g.clone()), (v, p)]), S256Point::mul_generator(&U256::from_u64(4462)));
+        assert!(S256Point::multi_mul(&[]).is_infinity());
+        assert!(S256Point::multi_mul(&[(Scalar::ONE, g.clone()), (-Scalar::ONE, g)]).is_infinity());
+    }
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+    }
+
+    fn bytes(hex: &str) -> Vec<u8> {
+        (0..hex.len()).step_by(2).map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap()).collect()
+    }
+
+    #[test]
+    fn test_sec() {
+        // Chapter 4 of the book
+        let cases = [
+            (U256::from_u64(5000), false,
+             "04ffe558e388852f0120e46af2d1b370f85854a8eb0841811ece0e3e03d282d57c315dc72890a4f10a1481c031b03b351b0dc79901ca18a00cf009dbdb157a1d10"),
+            (U256::from_u64(2018u64.pow(5)), false,
+             "04027f3da1918455e03c46f659266a1bb5204e959db7364d2f473bdf8f0a13cc9dff87647fd023c13b4a4994f17691895806e1b40b57f4fd22581a4f46851f3b06"),
+            (U256::from_u64(0xdeadbeef12345), false,
+             "04d90cd625ee87dd38656dd95cf79f65f60f7273b67d3096e68bd81e4f5342691f842efa762fd59961d0e99803c61edba8b3e3f7dc3a341836f97733aebf987121"),
+            (U256::from_u64(5001), true, "0357a4f368868a8a6d572991e484e664810ff14c05c0fa023275251151fe0e53d1"),
+            (U256::from_u64(2019u64.pow(5)), true, "02933ec2d2b111b92737ec12f1c5d20f3233a0ad21cd8b36d0bca7a0cfa5cb8701"),
+            (U256::from_u64(0xdeadbeef54321), true, "0296be5b1292f6c856b3c5654e886fc13511462059089cdf9c479623bfcbe77690"),
+        ];
+        for (secret, compressed, expected) in cases {
+            let point = S256Point::mul_generator(&secret);
+            assert_eq!(hex(&point.sec(compressed)), expected);
+            assert_eq!(S256Point::parse(&bytes(expected)), Ok(point));
+        }
+        assert_eq!(S256Point::infinity().sec(true), vec![0x00]);
+    }
+
+    #[test]
+    fn test_parse() {
+        // Both parities decompress to the right y
+        for k in 1..20u64 {
+            let point = S256Point::mul_generator(&U256::from_u64(k));
+            assert_eq!(S256Point::parse(&point.sec(true)), Ok(point.clone()));
+            assert_eq!(S256Point::parse(&point.sec(false)), Ok(point));
+        }
+
+        // Hybrid keys: the uncompressed form with the parity of y in the prefix
+        let g = S256Point::generator();
+        let mut hybrid = g.sec(false);
+        hybrid[0] = 0x06;
+        assert_eq!(S256Point::parse(&hybrid), Ok(g.clone()));
+        hybrid[0] = 0x07;
+        assert_eq!(S256Point::parse(&hybrid), Err("Hybrid prefix does not match the parity of y"));
+
+        let mut sec = g.sec(true);
+        assert_eq!(S256Point::parse(&sec[..32]), Err("Invalid SEC prefix or length"));
+        sec[0] = 0x04;
+        assert_eq!(S256Point::parse(&sec), Err("Invalid SEC prefix or length"));
+        assert_eq!(S256Point::parse(&[0x00]), Err("Invalid SEC prefix or length"));
+        assert_eq!(S256Point::parse(&[]), Err("Invalid SEC prefix or length"));
+
+        // x = 5 has no point (5^3 + 7 = 132 is not a square), x = p is not a field element
+        let mut off_curve = vec![0x02];
+        off_curve.extend(U256::from_u64(5).to_be_bytes());
+        assert_eq!(S256Point::parse(&off_curve), Err("Point is not on the curve"));
+        let mut too_large = vec![0x02];
+        too_large.extend(crate::field_5x52::P.to_be_bytes());
+        assert!(S256Point::parse(&too_large).is_err());
+        let mut uncompressed = g.sec(false);
+        uncompressed[64] ^= 1;
+        assert_eq!(S256Point::parse(&uncompressed), Err("Point is not on the curve"));
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            S256Point::generator().to_string(),
+            "S256Point(79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798, \
+             483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8)"
+        );
+        assert_eq!(S256Point::infinity().to_string(), "S256Point(infinity)");
+    }
+}
@@ -0,0 +1,343 @@
+/* Integers modulo the secp256k1 group order N, the counterpart of bitcoin-core/secp256k1's secp256k1_scalar (src/scalar.h).
+*
+*  ECDSA does all of its bookkeeping in this ring: the nonce inverse k^-1, r*d + z, and the low-S negation s -> N - s.
+*  A Scalar always holds its canonical value in [0, N), so comparisons and encodings need no reduction step. N is prime,
+*  so every non-zero scalar has an inverse.
+*
+*  Scalars hold private keys and nonces, so the arithmetic is constant-time like secp256k1's scalar_4x64_impl.h: sums
+*  and differences are corrected with a masked subtraction or addition of N instead of a branch, and products are reduced
+*  by folding instead of by the U256 division, whose steps depend on the value.
+*/
+
+use crate::s256::{S256Point, N};
+use crate::u256::{self, U256, U512};
+use std::fmt;
+use std::ops::{Add, Mul, Neg, Sub};
+
+// (N - 1) / 2, the largest scalar that is not "high"
+const HALF_N: U256 = U256::from_hex("7fffffffffffffffffffffffffffffff5d576e7357a4501ddfe92f46681b20a0");
+
+// 2^256 - N, a 129-bit number (SECP256K1_N_C_0 to SECP256K1_N_C_2)
+const N_C: [u64; 3] = [0x402da1732fc9bebf, 0x4551231950b75fc4, 1];
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Scalar(U256);
+
+impl Scalar {
+    pub const ZERO: Scalar = Scalar(U256::ZERO);
+    pub const ONE: Scalar = Scalar(U256::ONE);
+
+    /* Like secp256k1_scalar_set_b32 with an overflow check: values that are not below N are rejected. The check is the
+    *  borrow of value - N rather than a limb-by-limb comparison, so it takes the same time for every secret key and nonce.
+    */
+    pub fn from_u256(value: U256) -> Result<Scalar, &'static str> {
+        let (_, borrow) = value.overflowing_sub(&N);
+        if !borrow {
+            return Err("Scalar is not below the group order");
+        }
+        Ok(Scalar(value))
+    }
+
+    pub fn from_be_bytes(bytes: &[u8; 32]) -> Result<Scalar, &'static str> {
+        Scalar::from_u256(U256::from_be_bytes(bytes))
+    }
+
+    // Reduces instead of rejecting, for turning a message hash into the integer z
+    pub fn from_be_bytes_reduced(bytes: &[u8; 32]) -> Scalar {
+        Scalar(U256::from_be_bytes(bytes).div_rem(&N).1)
+    }
+
+    pub fn from_u64(n: u64) -> Scalar {
+        Scalar(U256::from_u64(n))
+    }
+
+    pub fn to_u256(self) -> U256 {
+        self.0
+    }
+
+    pub fn to_be_bytes(self) -> [u8; 32] {
+        self.0.to_be_bytes()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.0.is_zero()
+    }
+
+    // True for values above N/2, which low-S signatures replace by their negation
+    pub fn is_high(&self) -> bool {
+        self.0 > HALF_N
+    }
+
+    // Divstep inverse of the already reduced value, so the running time does not depend on it; zero has no inverse
+    pub fn inverse(&self) -> Result<Scalar, &'static str> {
+        match u256::inv_mod_divsteps_reduced(&self.0, &N) {
+            Some(inverse) if !self.is_zero() => Ok(Scalar(inverse)),
+            _ => Err("Cannot invert zero"),
+        }
+    }
+}
+
+impl Add<&Scalar> for &Scalar {
+    type Output = Scalar;
+
+    fn add(self, other: &Scalar) -> Scalar {
+        let (sum, carry) = self.0.overflowing_add(&other.0);
+        Scalar(reduce_once(&sum, carry))
+    }
+}
+
+impl Sub<&Scalar> for &Scalar {
+    type Output = Scalar;
+
+    fn sub(self, other: &Scalar) -> Scalar {
+        let (diff, borrow) = self.0.overflowing_sub(&other.0);
+        Scalar(select(mask(borrow), &diff.wrapping_add(&N), &diff))
+    }
+}
+
+impl Mul<&Scalar> for &Scalar {
+    type Output = Scalar;
+
+    fn mul(self, other: &Scalar) -> Scalar {
+        Scalar(reduce_512(&self.0.widening_mul(&other.0)))
+    }
+}
+
+impl Neg for &Scalar {
+    type Output = Scalar;
+
+    fn neg(self) -> Scalar {
+        Scalar::ZERO - self
+    }
+}
+
+// All ones when the flag is set and all zeros otherwise, for choosing between two values without a branch
+fn mask(flag: bool) -> u64 {
+    (flag as u64).wrapping_neg()
+}
+
+fn select(mask: u64, if_set: &U256, if_clear: &U256) -> U256 {
+    let (set, clear) = (if_set.limbs(), if_clear.limbs());
+    U256::from_limbs([0, 1, 2, 3].map(|i| (set[i] & mask) | (clear[i] & !mask)))
+}
+
+// value mod N for a value below 2N, where overflow is the 2^256 bit that did not fit in value
+fn reduce_once(value: &U256, overflow: bool) -> U256 {
+    let (reduced, borrow) = value.overflowing_sub(&N);
+    select(mask(overflow | !borrow), &reduced, value)
+}
+
+/* A 512-bit product modulo N, as secp256k1_scalar_reduce_512. Since 2^256 = N_C mod N, the high half can be folded onto
+*  the low half as high * N_C, which shrinks the number from 512 to 386, 260, 257 and finally 256 bits. All four folds
+*  are always done, followed by one masked subtraction of N, so nothing depends on how small the product already was.
+*/
+fn reduce_512(product: &U512) -> U256 {
+    let (low, high) = product.split();
+    let mut limbs = [0u64; 8];
+    limbs[..4].copy_from_slice(&low.limbs());
+    limbs[4..].copy_from_slice(&high.limbs());
+    for _ in 0..4 {
+        limbs = fold(&limbs);
+    }
+    debug_assert!(limbs[4..] == [0; 4], "four folds leave less than 2^256");
+    reduce_once(&U256::from_limbs([limbs[0], limbs[1], limbs[2], limbs[3]]), false)
+}
+
+// low + high * N_C for the low and high 256-bit halves of limbs
+fn fold(limbs: &[u64; 8]) -> [u64; 8] {
+    let mut result = [0u64; 8];
+    result[..4].copy_from_slice(&limbs[..4]);
+    for (i, &high) in limbs[4..].iter().enumerate() {
+        let mut carry: u128 = 0;
+        for (j, &c) in N_C.iter().enumerate() {
+            let t = high as u128 * c as u128 + result[i + j] as u128 + carry;
+            result[i + j] = t as u64;
+            carry = t >> 64;
+        }
+        for limb in result.iter_mut().skip(i + 3) {
+            let t = *limb as u128 + carry;
+            *limb = t as u64;
+            carry = t >> 64;
+        }
+    }
+    result
+}
+
+// The owned and mixed combinations all forward to the reference implementations above
+macro_rules! forward_scalar_op {
+    ($op_trait:ident, $op_fn:ident) => {
+        impl $op_trait<Scalar> for Scalar {
+            type Output = Scalar;
+
+            fn $op_fn(self, other: Scalar) -> Scalar {
+                $op_trait::$op_fn(&self, &other)
+            }
+        }
+
+        impl<'b> $op_trait<&'b Scalar> for Scalar {
+            type Output = Scalar;
+
+            fn $op_fn(self, other: &'b Scalar) -> Scalar {
+                $op_trait::$op_fn(&self, other)
+            }
+        }
+
+        impl<'a> $op_trait<Scalar> for &'a Scalar {
+            type Output = Scalar;
+
+            fn $op_fn(self, other: Scalar) -> Scalar {
+                $op_trait::$op_fn(self, &other)
+            }
+        }
+    };
+}
+
+forward_scalar_op!(Add, add);
+forward_scalar_op!(Sub, sub);
+forward_scalar_op!(Mul, mul);
+
+impl Neg for Scalar {
+    type Output = Scalar;
+
+    fn neg(self) -> Scalar {
+        -&self
+    }
+}
+
+// k * P, as written in the book. A Scalar may be a secret, so this is the constant-time multiplication
+impl Mul<&S256Point> for &Scalar {
+    type Output = S256Point;
+
+    fn mul(self, point: &S256Point) -> S256Point {
+        point.mul_secret(self)
+    }
+}
+
+impl Mul<S256Point> for Scalar {
+    type Output = S256Point;
+
+    fn mul(self, point: S256Point) -> S256Point {
+        point.mul_secret(&self)
+    }
+}
+
+/* Scalars hold private keys and nonces, so Debug does not print the value: it ends up in panic messages and logs that
+*  nobody meant to put a secret in. Display and to_u256 are there for code that needs the value on purpose.
+*/
+impl fmt::Debug for Scalar {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Scalar(..)")
+    }
+}
+
+impl fmt::Display for Scalar {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:064x}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_bytes_overflow() {
+        assert_eq!(Scalar::from_u256(N), Err("Scalar is not below the group order"));
+        assert!(Scalar::from_be_bytes(&[0xff; 32]).is_err());
+        assert!(Scalar::from_u256(N - U256::ONE).is_ok());
+        // 2^256 - 1 is reduced instead of rejected
+        assert_eq!(Scalar::from_be_bytes_reduced(&[0xff; 32]).to_u256(), U256::MAX - N);
+        let bytes = Scalar::from_u64(0x1234).to_be_bytes();
+        assert_eq!(Scalar::from_be_bytes(&bytes), Ok(Scalar::from_u64(0x1234)));
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let minus_one = Scalar::from_u256(N - U256::ONE).unwrap();
+        assert_eq!(minus_one + Scalar::ONE, Scalar::ZERO);
+        assert_eq!(Scalar::ZERO - Scalar::ONE, minus_one);
+        assert_eq!(-Scalar::ONE, minus_one);
+        assert_eq!(-Scalar::ZERO, Scalar::ZERO);
+        assert_eq!(minus_one * minus_one, Scalar::ONE);
+        assert_eq!(Scalar::from_u64(6) * Scalar::from_u64(7), Scalar::from_u64(42));
+    }
+
+    // Deterministic pseudo-random scalars for comparing against the variable-time U256 arithmetic
+    fn samples() -> Vec<U256> {
+        let mut state: u64 = 0x853c49e6748fea9b;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+        let mut values = vec![U256::ZERO, U256::ONE, N - U256::ONE, N - U256::from_u64(2), HALF_N, HALF_N + U256::ONE];
+        for _ in 0..40 {
+            values.push(U256::from_limbs([next(), next(), next(), next()]) % N);
+        }
+        values
+    }
+
+    #[test]
+    fn test_matches_reference() {
+        let values = samples();
+        for a in &values {
+            for b in &values {
+                let x = Scalar::from_u256(*a).unwrap();
+                let y = Scalar::from_u256(*b).unwrap();
+                assert_eq!((x * y).to_u256(), a.mul_mod(b, &N));
+                assert_eq!((x + y).to_u256(), a.add_mod(b, &N));
+                assert_eq!((x - y).to_u256(), a.sub_mod(b, &N));
+            }
+            assert_eq!((-Scalar::from_u256(*a).unwrap()).to_u256(), U256::ZERO.sub_mod(a, &N));
+        }
+    }
+
+    #[test]
+    fn test_reduce_512() {
+        // The largest 512-bit value needs all four folds
+        assert_eq!(reduce_512(&U256::MAX.widening_mul(&U256::MAX)), U256::MAX.mul_mod(&U256::MAX, &N));
+        assert_eq!(reduce_512(&U512::from_u256(&N)), U256::ZERO);
+        assert_eq!(reduce_512(&U512::from_u256(&U256::MAX)), U256::MAX - N);
+        assert_eq!(U256::ZERO.wrapping_sub(&N), U256::from_limbs([N_C[0], N_C[1], N_C[2], 0]));
+    }
+
+    #[test]
+    fn test_inverse() {
+        assert!(Scalar::ZERO.inverse().is_err());
+        for value in [1u64, 2, 3, 0xdeadbeef] {
+            let scalar = Scalar::from_u64(value);
+            assert_eq!(scalar * scalar.inverse().unwrap(), Scalar::ONE);
+        }
+        let big = Scalar::from_u256(N - U256::from_u64(12345)).unwrap();
+        assert_eq!(big * big.inverse().unwrap(), Scalar::ONE);
+    }
+
+    #[test]
+    fn test_is_high() {
+        assert!(!Scalar::from_u256(HALF_N).unwrap().is_high());
+        assert!(Scalar::from_u256(HALF_N + U256::ONE).unwrap().is_high());
+        assert!(!Scalar::ONE.is_high());
+        // Negating a non-zero scalar always flips it between the two halves
+        let s = Scalar::from_u256(HALF_N + U256::from_u64(5)).unwrap();
+        assert!(!(-s).is_high());
+        assert_eq!(HALF_N + HALF_N + U256::ONE, N);
+    }
+
+    #[test]
+    fn test_debug_is_redacted() {
+        let secret = Scalar::from_u64(0xdeadbeef);
+        assert_eq!(format!("{:?}", secret), "Scalar(..)");
+        assert_eq!(format!("{:?}", Some(secret)), "Some(Scalar(..))");
+        assert!(secret.to_string().ends_with("deadbeef"));
+    }
+
+    #[test]
+    fn test_point_multiplication() {
+        let g = S256Point::generator();
+        assert_eq!(&Scalar::from_u64(7) * &g, g.rmul(&U256::from_u64(7)));
+        assert!((Scalar::ZERO * g.clone()).is_infinity());
+        let minus_one = -Scalar::ONE;
+        assert!((&minus_one * &g).add(&g).is_infinity());
+    }
+}
@@ -0,0 +1,776 @@
+/* Fixed-width unsigned integers used as the backing store for field elements.
+*
+*  Jimmy Song's Python code gets arbitrary-precision integers for free, but Rust's widest primitive is u128, which is not enough
+*  to hold a 256-bit secp256k1 coordinate, let alone the product of two of them. U256 keeps four 64-bit limbs in little-endian
+*  order (limb 0 is the least significant), and U512 holds the full-width product of two U256 values so it can be reduced
+*  modulo a 256-bit prime without losing any bits.
+*
+*  Division follows Knuth's Algorithm D (The Art of Computer Programming, Vol. 2, 4.3.1) using u128 for the two-limb estimates,
+*  the same approach Hacker's Delight takes in its divmnu routine.
+*/
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Div, Mul, Rem, Shl, Shr, Sub};
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct U256([u64; 4]);
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct U512([u64; 8]);
+
+impl U256 {
+    pub const ZERO: U256 = U256([0; 4]);
+    pub const ONE: U256 = U256([1, 0, 0, 0]);
+    pub const MAX: U256 = U256([u64::MAX; 4]);
+
+    pub const fn from_u64(n: u64) -> Self {
+        U256([n, 0, 0, 0])
+    }
+
+    // Builds a U256 from little-endian 64-bit limbs
+    pub const fn from_limbs(limbs: [u64; 4]) -> Self {
+        U256(limbs)
+    }
+
+    pub const fn limbs(&self) -> [u64; 4] {
+        self.0
+    }
+
+    // Parses a big-endian hex string without a 0x prefix. It is a const fn so curve constants can be written the way
+    // they are published; an invalid digit or a string longer than 64 digits is a programming error and panics.
+    pub const fn from_hex(hex: &str) -> Self {
+        let bytes = hex.as_bytes();
+        assert!(bytes.len() <= 64, "hex string longer than 256 bits");
+        let mut limbs = [0u64; 4];
+        let mut i = 0;
+        while i < bytes.len() {
+            let c = bytes[bytes.len() - 1 - i];
+            let nibble = match c {
+                b'0'..=b'9' => c - b'0',
+                b'a'..=b'f' => c - b'a' + 10,
+                b'A'..=b'F' => c - b'A' + 10,
+                _ => panic!("invalid hex digit"),
+            };
+            limbs[i / 16] |= (nibble as u64) << ((i % 16) * 4);
+            i += 1;
+        }
+        U256(limbs)
+    }
+
+    pub fn from_be_bytes(bytes: &[u8; 32]) -> Self {
+        let mut limbs = [0u64; 4];
+        for (i, limb) in limbs.iter_mut().enumerate() {
+            let start = 32 - (i + 1) * 8;
+            let mut word = [0u8; 8];
+            word.copy_from_slice(&bytes[start..start + 8]);
+            *limb = u64::from_be_bytes(word);
+        }
+        U256(limbs)
+    }
+
+    pub fn to_be_bytes(self) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        for (i, limb) in self.0.iter().enumerate() {
+            let start = 32 - (i + 1) * 8;
+            bytes[start..start + 8].copy_from_slice(&limb.to_be_bytes());
+        }
+        bytes
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.0 == [0; 4]
+    }
+
+    pub fn is_odd(&self) -> bool {
+        self.0[0] & 1 == 1
+    }
+
+    // Returns the lowest 64 bits, discarding the rest
+    pub fn low_u64(&self) -> u64 {
+        self.0[0]
+    }
+
+    // Number of bits needed to represent the value (0 for zero)
+    pub fn bits(&self) -> u32 {
+        for i in (0..4).rev() {
+            if self.0[i] != 0 {
+                return 64 * i as u32 + (64 - self.0[i].leading_zeros());
+            }
+        }
+        0
+    }
+
+    // Returns bit `i`, counting from the least significant bit
+    pub fn bit(&self, i: u32) -> bool {
+        if i >= 256 {
+            return false;
+        }
+        (self.0[(i / 64) as usize] >> (i % 64)) & 1 == 1
+    }
+
+    pub fn overflowing_add(&self, other: &U256) -> (U256, bool) {
+        let mut result = [0u64; 4];
+        let mut carry = false;
+        for (i, limb) in result.iter_mut().enumerate() {
+            let (sum, c1) = self.0[i].overflowing_add(other.0[i]);
+            let (sum, c2) = sum.overflowing_add(carry as u64);
+            *limb = sum;
+            carry = c1 || c2;
+        }
+        (U256(result), carry)
+    }
+
+    pub fn overflowing_sub(&self, other: &U256) -> (U256, bool) {
+        let mut result = [0u64; 4];
+        let mut borrow = false;
+        for (i, limb) in result.iter_mut().enumerate() {
+            let (diff, b1) = self.0[i].overflowing_sub(other.0[i]);
+            let (diff, b2) = diff.overflowing_sub(borrow as u64);
+            *limb = diff;
+            borrow = b1 || b2;
+        }
+        (U256(result), borrow)
+    }
+
+    pub fn wrapping_add(&self, other: &U256) -> U256 {
+        self.overflowing_add(other).0
+    }
+
+    pub fn wrapping_sub(&self, other: &U256) -> U256 {
+        self.overflowing_sub(other).0
+    }
+
+    // Full 256x256 -> 512-bit schoolbook multiplication, so no product ever overflows
+    pub fn widening_mul(&self, other: &U256) -> U512 {
+        let mut result = [0u64; 8];
+        for i in 0..4 {
+            let mut carry: u128 = 0;
+            for j in 0..4 {
+                let t = self.0[i] as u128 * other.0[j] as u128 + result[i + j] as u128 + carry;
+                result[i + j] = t as u64;
+                carry = t >> 64;
+            }
+            result[i + 4] = carry as u64;
+        }
+        U512(result)
+    }
+
+    // Returns (self / divisor, self % divisor). Panics when the divisor is zero, like the primitive integer types.
+    pub fn div_rem(&self, divisor: &U256) -> (U256, U256) {
+        let (quotient, remainder) = div_rem_limbs(&self.0, divisor);
+        (U256([quotient[0], quotient[1], quotient[2], quotient[3]]), remainder)
+    }
+
+    /* Modular helpers. All of them expect their operands to already be reduced below `modulus`,
+    *  which is what the FieldElement constructor guarantees. They branch on the values (and mul_mod divides), so they are
+    *  for public values only; secrets go through Scalar and S256Field, which do the same arithmetic in constant time.
+    */
+    pub fn add_mod(&self, other: &U256, modulus: &U256) -> U256 {
+        let (sum, carry) = self.overflowing_add(other);
+        if carry || sum >= *modulus {
+            sum.wrapping_sub(modulus)
+        } else {
+            sum
+        }
+    }
+
+    pub fn sub_mod(&self, other: &U256, modulus: &U256) -> U256 {
+        let (diff, borrow) = self.overflowing_sub(other);
+        if borrow {
+            diff.wrapping_add(modulus)
+        } else {
+            diff
+        }
+    }
+
+    pub fn mul_mod(&self, other: &U256, modulus: &U256) -> U256 {
+        self.widening_mul(other).rem(modulus)
+    }
+
+    // Left-to-right square-and-multiply over every bit of the exponent
+    pub fn pow_mod(&self, exp: &U256, modulus: &U256) -> U256 {
+        let mut result = U256::ONE.div_rem(modulus).1;
+        for i in (0..exp.bits()).rev() {
+            result = result.mul_mod(&result, modulus);
+            if exp.bit(i) {
+                result = result.mul_mod(self, modulus);
+            }
+        }
+        result
+    }
+
+    // Short division by a single limb, used for decimal formatting
+    fn div_rem_u64(&self, divisor: u64) -> (U256, u64) {
+        let mut quotient = [0u64; 4];
+        let mut remainder: u128 = 0;
+        for i in (0..4).rev() {
+            let current = (remainder << 64) | self.0[i] as u128;
+            quotient[i] = (current / divisor as u128) as u64;
+            remainder = current % divisor as u128;
+        }
+        (U256(quotient), remainder as u64)
+    }
+}
+
+impl U512 {
+    pub fn from_u256(low: &U256) -> Self {
+        let mut limbs = [0u64; 8];
+        limbs[..4].copy_from_slice(&low.0);
+        U512(limbs)
+    }
+
+    // Splits into (low, high) 256-bit halves
+    pub fn split(&self) -> (U256, U256) {
+        (
+            U256([self.0[0], self.0[1], self.0[2], self.0[3]]),
+            U256([self.0[4], self.0[5], self.0[6], self.0[7]]),
+        )
+    }
+
+    pub fn div_rem(&self, divisor: &U256) -> (U512, U256) {
+        let (quotient, remainder) = div_rem_limbs(&self.0, divisor);
+        (U512(quotient), remainder)
+    }
+
+    // Reduces a double-width product modulo a 256-bit value
+    pub fn rem(&self, modulus: &U256) -> U256 {
+        self.div_rem(modulus).1
+    }
+}
+
+/* Knuth's Algorithm D for an up-to-8-limb numerator and an up-to-4-limb divisor.
+*
+*  The divisor is shifted left until its top bit is set, so each quotient limb estimated from the top two numerator limbs is at
+*  most two too large; the estimate is corrected against the second divisor limb first and, in the rare case it is still one too
+*  large, the multiply-and-subtract step goes negative and the divisor is added back.
+*/
+fn div_rem_limbs(numerator: &[u64], divisor: &U256) -> ([u64; 8], U256) {
+    let d = divisor.0;
+    let n = match d.iter().rposition(|&limb| limb != 0) {
+        Some(top) => top + 1,
+        None => panic!("attempt to divide by zero"),
+    };
+    let m = numerator.len();
+    let mut quotient = [0u64; 8];
+
+    // Single-limb divisors only need short division
+    if n == 1 {
+        let mut remainder: u128 = 0;
+        for i in (0..m).rev() {
+            let current = (remainder << 64) | numerator[i] as u128;
+            quotient[i] = (current / d[0] as u128) as u64;
+            remainder = current % d[0] as u128;
+        }
+        return (quotient, U256::from_u64(remainder as u64));
+    }
+
+    if m < n {
+        let mut remainder = [0u64; 4];
+        remainder[..m].copy_from_slice(numerator);
+        return (quotient, U256(remainder));
+    }
+
+    // Normalize so the divisor's top limb has its most significant bit set
+    let shift = d[n - 1].leading_zeros();
+    let mut v = [0u64; 4];
+    let mut u = [0u64; 9];
+    for i in (0..n).rev() {
+        v[i] = d[i] << shift;
+        if shift > 0 && i > 0 {
+            v[i] |= d[i - 1] >> (64 - shift);
+        }
+    }
+    u[m] = if shift > 0 { numerator[m - 1] >> (64 - shift) } else { 0 };
+    for i in (0..m).rev() {
+        u[i] = numerator[i] << shift;
+        if shift > 0 && i > 0 {
+            u[i] |= numerator[i - 1] >> (64 - shift);
+        }
+    }
+
+    let base: u128 = 1 << 64;
+    for j in (0..=m - n).rev() {
+        // Estimate the quotient limb from the top two limbs of the current remainder
+        let top = ((u[j + n] as u128) << 64) | u[j + n - 1] as u128;
+        let mut qhat = top / v[n - 1] as u128;
+        let mut rhat = top % v[n - 1] as u128;
+        while qhat >= base || qhat * v[n - 2] as u128 > ((rhat << 64) | u[j + n - 2] as u128) {
+            qhat -= 1;
+            rhat += v[n - 1] as u128;
+            if rhat >= base {
+                break;
+            }
+        }
+
+        // Multiply and subtract qhat * v from the current window of u
+        let mut borrow: i128 = 0;
+        for i in 0..n {
+            let product = qhat * v[i] as u128;
+            let t = u[i + j] as i128 - borrow - (product as u64) as i128;
+            u[i + j] = t as u64;
+            borrow = (product >> 64) as i128 - (t >> 64);
+        }
+        let t = u[j + n] as i128 - borrow;
+        u[j + n] = t as u64;
+
+        quotient[j] = qhat as u64;
+        if t < 0 {
+            // The estimate was one too large: add the divisor back once
+            quotient[j] = quotient[j].wrapping_sub(1);
+            let mut carry: u128 = 0;
+            for i in 0..n {
+                let sum = u[i + j] as u128 + v[i] as u128 + carry;
+                u[i + j] = sum as u64;
+                carry = sum >> 64;
+            }
+            u[j + n] = u[j + n].wrapping_add(carry as u64);
+        }
+    }
+
+    // Undo the normalization shift on the remainder
+    let mut remainder = [0u64; 4];
+    for i in 0..n {
+        remainder[i] = u[i] >> shift;
+        if shift > 0 {
+            remainder[i] |= u[i + 1] << (64 - shift);
+        }
+    }
+    (quotient, U256(remainder))
+}
+
+/* Modular inverse with Bernstein-Yang divsteps ("safegcd"), as described in bitcoin-core/secp256k1's
+*  doc/safegcd_implementation.md. This is the plain one-divstep-per-iteration form of the algorithm, without the batching
+*  into 62-step transition matrices that the C library adds for speed:
+*
+*      delta, f, g, d, e = 1, M, x, 0, 1
+*      repeat: if delta > 0 and g odd: delta, f, g, d, e = 1 - delta, g, (g - f) / 2, e, (e - d) / 2
+*              elif g odd:              delta, f, g, d, e = 1 + delta, f, (g + f) / 2, d, (e + d) / 2
+*              else:                    delta, f, g, d, e = 1 + delta, f, g / 2,       d, e / 2
+*
+*  with d and e tracked modulo M so that d = f/x and e = g/x hold throughout. Once g reaches zero, |f| is gcd(x, M), so the
+*  inverse exists exactly when f = +-1, and it is then d * f.
+*
+*  The number of iterations only depends on the bit length of M (the bound proven in the paper), and every step is done
+*  with masks instead of branches. M must be odd. This entry point first reduces x with the variable-time division, so
+*  only inv_mod_divsteps_reduced, which takes x already below M, has a running time that does not depend on x.
+*/
+pub fn inv_mod_divsteps(x: &U256, modulus: &U256) -> Option<U256> {
+    assert!(modulus.is_odd(), "divsteps need an odd modulus");
+    inv_mod_divsteps_reduced(&x.div_rem(modulus).1, modulus)
+}
+
+// The divstep inverse of an x that is already below M, with no division up front
+pub fn inv_mod_divsteps_reduced(x: &U256, modulus: &U256) -> Option<U256> {
+    assert!(modulus.is_odd(), "divsteps need an odd modulus");
+    debug_assert!(x < modulus, "x is not reduced below the modulus");
+    let m = *modulus;
+
+    // f and g are signed, so they live in 320-bit two's complement; |f|, |g| never exceed M
+    let mut f = Signed320::from_u256(&m);
+    let mut g = Signed320::from_u256(x);
+    let mut d = U256::ZERO;
+    let mut e = U256::ONE;
+    let mut delta: i64 = 1;
+
+    let bits = m.bits() as u64;
+    let iterations = if bits < 46 { (49 * bits + 80) / 17 } else { (49 * bits + 57) / 17 };
+    for _ in 0..iterations {
+        // swap_mask is all ones when delta > 0 and g is odd
+        let g_odd = (g.0[0] & 1).wrapping_neg();
+        let delta_positive = ((-delta) >> 63) as u64;
+        let swap_mask = g_odd & delta_positive;
+
+        // Conditionally replace (delta, f, g, d, e) with (-delta, g, -f, e, -d), which turns the first case into the second
+        let negated_f = f.negate();
+        let negated_d = neg_mod_masked(&d, &m);
+        let (new_f, new_g) = (Signed320::select(swap_mask, &g, &f), Signed320::select(swap_mask, &negated_f, &g));
+        let (new_d, new_e) = (select_u256(swap_mask, &e, &d), select_u256(swap_mask, &negated_d, &e));
+        f = new_f;
+        g = new_g;
+        d = new_d;
+        e = new_e;
+        delta = (delta & !(swap_mask as i64)) | (-delta & swap_mask as i64);
+
+        // If g is odd, add f to it (and d to e) so it becomes even
+        g = g.add(&Signed320::select(g_odd, &f, &Signed320::ZERO));
+        e = add_mod_masked(&e, &select_u256(g_odd, &d, &U256::ZERO), &m);
+
+        // Halve g exactly and e modulo M
+        g = g.shr1();
+        e = half_mod_masked(&e, &m);
+        delta += 1;
+    }
+
+    // f is now +-gcd(x, M)
+    let one = Signed320::from_u256(&U256::ONE);
+    if f == one {
+        Some(d)
+    } else if f == one.negate() {
+        Some(neg_mod_masked(&d, &m))
+    } else {
+        None
+    }
+}
+
+// A signed 320-bit integer in two's complement, only used for the f and g of the divstep inverse
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct Signed320([u64; 5]);
+
+impl Signed320 {
+    const ZERO: Signed320 = Signed320([0; 5]);
+
+    fn from_u256(value: &U256) -> Self {
+        Signed320([value.0[0], value.0[1], value.0[2], value.0[3], 0])
+    }
+
+    fn select(mask: u64, if_set: &Signed320, if_clear: &Signed320) -> Signed320 {
+        let mut result = [0u64; 5];
+        for (i, limb) in result.iter_mut().enumerate() {
+            *limb = (if_set.0[i] & mask) | (if_clear.0[i] & !mask);
+        }
+        Signed320(result)
+    }
+
+    fn add(&self, other: &Signed320) -> Signed320 {
+        let mut result = [0u64; 5];
+        let mut carry = 0u64;
+        for (i, limb) in result.iter_mut().enumerate() {
+            let sum = self.0[i] as u128 + other.0[i] as u128 + carry as u128;
+            *limb = sum as u64;
+            carry = (sum >> 64) as u64;
+        }
+        Signed320(result)
+    }
+
+    fn negate(&self) -> Signed320 {
+        let mut inverted = [0u64; 5];
+        for (i, limb) in inverted.iter_mut().enumerate() {
+            *limb = !self.0[i];
+        }
+        Signed320(inverted).add(&Signed320([1, 0, 0, 0, 0]))
+    }
+
+    // Arithmetic shift right by one, keeping the sign
+    fn shr1(&self) -> Signed320 {
+        let mut result = [0u64; 5];
+        for (i, limb) in result.iter_mut().enumerate().take(4) {
+            *limb = (self.0[i] >> 1) | (self.0[i + 1] << 63);
+        }
+        result[4] = ((self.0[4] as i64) >> 1) as u64;
+        Signed320(result)
+    }
+}
+
+fn select_u256(mask: u64, if_set: &U256, if_clear: &U256) -> U256 {
+    let mut result = [0u64; 4];
+    for (i, limb) in result.iter_mut().enumerate() {
+        *limb = (if_set.0[i] & mask) | (if_clear.0[i] & !mask);
+    }
+    U256(result)
+}
+
+// (a + b) mod m for a, b < m, choosing between the sum and the reduced sum without a branch
+fn add_mod_masked(a: &U256, b: &U256, m: &U256) -> U256 {
+    let (sum, carry) = a.overflowing_add(b);
+    let (reduced, borrow) = sum.overflowing_sub(m);
+    let use_reduced = ((carry | !borrow) as u64).wrapping_neg();
+    select_u256(use_reduced, &reduced, &sum)
+}
+
+// -a mod m for a < m; zero stays zero
+fn neg_mod_masked(a: &U256, m: &U256) -> U256 {
+    let nonzero = ((!a.is_zero()) as u64).wrapping_neg();
+    select_u256(nonzero, &m.wrapping_sub(a), &U256::ZERO)
+}
+
+// a / 2 mod m for odd m: add m first when a is odd, then shift the 257-bit sum right by one
+fn half_mod_masked(a: &U256, m: &U256) -> U256 {
+    let odd = (a.0[0] & 1).wrapping_neg();
+    let (sum, carry) = a.overflowing_add(&select_u256(odd, m, &U256::ZERO));
+    let mut result = sum >> 1;
+    result.0[3] |= (carry as u64) << 63;
+    result
+}
+
+impl Ord for U256 {
+    fn cmp(&self, other: &Self) -> Ordering {
+        for i in (0..4).rev() {
+            match self.0[i].cmp(&other.0[i]) {
+                Ordering::Equal => continue,
+                ordering => return ordering,
+            }
+        }
+        Ordering::Equal
+    }
+}
+
+impl PartialOrd for U256 {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl From<u64> for U256 {
+    fn from(n: u64) -> Self {
+        U256::from_u64(n)
+    }
+}
+
+/* The operator traits behave like the primitive integers in a debug build: overflow and division by zero panic.
+*  Code that expects to wrap should call the overflowing or wrapping methods instead.
+*/
+impl Add for U256 {
+    type Output = U256;
+
+    fn add(self, other: U256) -> U256 {
+        let (sum, carry) = self.overflowing_add(&other);
+        assert!(!carry, "attempt to add with overflow");
+        sum
+    }
+}
+
+impl Sub for U256 {
+    type Output = U256;
+
+    fn sub(self, other: U256) -> U256 {
+        let (diff, borrow) = self.overflowing_sub(&other);
+        assert!(!borrow, "attempt to subtract with overflow");
+        diff
+    }
+}
+
+impl Mul for U256 {
+    type Output = U256;
+
+    fn mul(self, other: U256) -> U256 {
+        let (low, high) = self.widening_mul(&other).split();
+        assert!(high.is_zero(), "attempt to multiply with overflow");
+        low
+    }
+}
+
+impl Div for U256 {
+    type Output = U256;
+
+    fn div(self, other: U256) -> U256 {
+        self.div_rem(&other).0
+    }
+}
+
+impl Rem for U256 {
+    type Output = U256;
+
+    fn rem(self, other: U256) -> U256 {
+        self.div_rem(&other).1
+    }
+}
+
+impl Shl<u32> for U256 {
+    type Output = U256;
+
+    fn shl(self, shift: u32) -> U256 {
+        if shift >= 256 {
+            return U256::ZERO;
+        }
+        let limb_shift = (shift / 64) as usize;
+        let bit_shift = shift % 64;
+        let mut result = [0u64; 4];
+        for (i, limb) in result.iter_mut().enumerate().skip(limb_shift) {
+            *limb = self.0[i - limb_shift] << bit_shift;
+            if bit_shift > 0 && i > limb_shift {
+                *limb |= self.0[i - limb_shift - 1] >> (64 - bit_shift);
+            }
+        }
+        U256(result)
+    }
+}
+
+impl Shr<u32> for U256 {
+    type Output = U256;
+
+    fn shr(self, shift: u32) -> U256 {
+        if shift >= 256 {
+            return U256::ZERO;
+        }
+        let limb_shift = (shift / 64) as usize;
+        let bit_shift = shift % 64;
+        let mut result = [0u64; 4];
+        for (i, limb) in result.iter_mut().enumerate().take(4 - limb_shift) {
+            *limb = self.0[i + limb_shift] >> bit_shift;
+            if bit_shift > 0 && i + limb_shift + 1 < 4 {
+                *limb |= self.0[i + limb_shift + 1] << (64 - bit_shift);
+            }
+        }
+        U256(result)
+    }
+}
+
+// Decimal output, matching how Python prints the book's integers
+impl fmt::Display for U256 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_zero() {
+            return f.pad_integral(true, "", "0");
+        }
+        // Peel off 19 decimal digits at a time, the largest power of ten that fits in a u64
+        const CHUNK: u64 = 10_000_000_000_000_000_000;
+        let mut chunks = Vec::new();
+        let mut rest = *self;
+        while !rest.is_zero() {
+            let (quotient, remainder) = rest.div_rem_u64(CHUNK);
+            chunks.push(remainder);
+            rest = quotient;
+        }
+        let mut digits = chunks.pop().unwrap().to_string();
+        for chunk in chunks.iter().rev() {
+            digits.push_str(&format!("{:019}", chunk));
+        }
+        f.pad_integral(true, "", &digits)
+    }
+}
+
+impl fmt::LowerHex for U256 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut digits = String::new();
+        for limb in self.0.iter().rev() {
+            if digits.is_empty() {
+                if *limb != 0 {
+                    digits = format!("{:x}", limb);
+                }
+            } else {
+                digits.push_str(&format!("{:016x}", limb));
+            }
+        }
+        if digits.is_empty() {
+            digits.push('0');
+        }
+        f.pad_integral(true, "0x", &digits)
+    }
+}
+
+impl fmt::Debug for U256 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "U256({:#x})", self)
+    }
+}
+
+impl fmt::Debug for U512 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (low, high) = self.split();
+        write!(f, "U512({:#x}, {:#x})", high, low)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const P: U256 = U256::from_hex("fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f");
+
+    #[test]
+    fn test_from_hex_and_bytes_roundtrip() {
+        let n = U256::from_hex("79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798");
+        let bytes = n.to_be_bytes();
+        assert_eq!(bytes[0], 0x79);
+        assert_eq!(bytes[31], 0x98);
+        assert_eq!(U256::from_be_bytes(&bytes), n);
+        assert_eq!(format!("{:x}", n), "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798");
+    }
+
+    #[test]
+    fn test_display_decimal() {
+        assert_eq!(U256::ZERO.to_string(), "0");
+        assert_eq!(U256::from_u64(223).to_string(), "223");
+        assert_eq!(
+            U256::MAX.to_string(),
+            "115792089237316195423570985008687907853269984665640564039457584007913129639935"
+        );
+    }
+
+    #[test]
+    fn test_add_sub_carry() {
+        let (sum, carry) = U256::MAX.overflowing_add(&U256::ONE);
+        assert_eq!(sum, U256::ZERO);
+        assert!(carry);
+        let (diff, borrow) = U256::ZERO.overflowing_sub(&U256::ONE);
+        assert_eq!(diff, U256::MAX);
+        assert!(borrow);
+        assert_eq!(U256::from_limbs([u64::MAX, 0, 0, 0]) + U256::ONE, U256::from_limbs([0, 1, 0, 0]));
+    }
+
+    #[test]
+    fn test_widening_mul() {
+        // (2^256 - 1)^2 = 2^512 - 2^257 + 1
+        let (low, high) = U256::MAX.widening_mul(&U256::MAX).split();
+        assert_eq!(low, U256::ONE);
+        assert_eq!(high, U256::MAX - U256::ONE);
+    }
+
+    #[test]
+    fn test_div_rem() {
+        let a = U256::from_hex("c7207fee197d27c618aea621406f6bf5ef6fca38681d82b2f06fddbdce6feab6");
+        let b = U256::from_hex("eff69ef2b1bd93a66ed5219add4fb51e11a840f4");
+        let (q, r) = a.div_rem(&b);
+        assert!(r < b);
+        assert_eq!(q * b + r, a);
+        assert_eq!(U256::from_u64(1000).div_rem(&U256::from_u64(7)), (U256::from_u64(142), U256::from_u64(6)));
+        assert_eq!(b.div_rem(&a), (U256::ZERO, b));
+    }
+
+    #[test]
+    fn test_u512_rem() {
+        // (p - 1)^2 = 1 mod p
+        let p_minus_one = P - U256::ONE;
+        assert_eq!(p_minus_one.mul_mod(&p_minus_one, &P), U256::ONE);
+        // The secp256k1 generator satisfies y^2 = x^3 + 7 mod p
+        let x = U256::from_hex("79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798");
+        let y = U256::from_hex("483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8");
+        let x_cubed = x.mul_mod(&x, &P).mul_mod(&x, &P);
+        assert_eq!(y.mul_mod(&y, &P), x_cubed.add_mod(&U256::from_u64(7), &P));
+    }
+
+    #[test]
+    fn test_shifts() {
+        assert_eq!(U256::ONE << 255 >> 255, U256::ONE);
+        assert_eq!(U256::ONE << 64, U256::from_limbs([0, 1, 0, 0]));
+        assert_eq!(U256::MAX >> 200, U256::from_u64((1 << 56) - 1));
+        assert_eq!(U256::ONE << 256, U256::ZERO);
+    }
+
+    #[test]
+    fn test_inv_mod_divsteps() {
+        let values = [
+            U256::ONE,
+            U256::from_u64(2),
+            P - U256::ONE,
+            U256::from_hex("deadbeef12345"),
+            U256::from_hex("79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798"),
+        ];
+        for x in values.iter() {
+            let inverse = inv_mod_divsteps(x, &P).unwrap();
+            assert_eq!(x.mul_mod(&inverse, &P), U256::ONE);
+        }
+        // Small moduli, including composite ones where only the coprime values are invertible
+        for m in [3u64, 13, 15, 223, 225] {
+            let modulus = U256::from_u64(m);
+            for x in 0..m {
+                let gcd_is_one = (1..m).any(|y| x * y % m == 1);
+                match inv_mod_divsteps(&U256::from_u64(x), &modulus) {
+                    Some(inverse) => assert_eq!(x * inverse.low_u64() % m, 1),
+                    None => assert!(!gcd_is_one),
+                }
+            }
+        }
+        assert_eq!(inv_mod_divsteps(&U256::ZERO, &P), None);
+        // Values above the modulus are reduced first
+        assert_eq!(inv_mod_divsteps(&(P + U256::from_u64(2)), &P), inv_mod_divsteps_reduced(&U256::from_u64(2), &P));
+    }
+
+    #[test]
+    fn test_pow_mod_fermat() {
+        // Fermat's little theorem: a^(p-1) = 1 mod p
+        let a = U256::from_hex("deadbeef12345");
+        assert_eq!(a.pow_mod(&(P - U256::ONE), &P), U256::ONE);
+        assert_eq!(U256::from_u64(3).pow_mod(&U256::from_u64(5), &U256::from_u64(223)), U256::from_u64(243 % 223));
+    }
+}